@@ -1,3 +1,4 @@
+use aoc_2024::day_06::PatrolMethod;
 use criterion;
 
 fn bench_part_a(bench: &mut criterion::Criterion) {
@@ -14,5 +15,30 @@ fn bench_part_b(bench: &mut criterion::Criterion) {
     });
 }
 
-criterion::criterion_group!(benches, bench_part_a, bench_part_b);
+fn bench_patrol_slow_vs_fast(bench: &mut criterion::Criterion) {
+    // Both simulations must agree before comparing them makes any sense.
+    let looping = ".#...\n....#\n#^...\n...#.\n";
+    assert!(aoc_2024::day_06::patrol_loops(looping, PatrolMethod::Slow));
+    assert!(aoc_2024::day_06::patrol_loops(looping, PatrolMethod::Fast));
+
+    let input: String = util::read_resource("day_06.txt").unwrap();
+    assert_eq!(
+        aoc_2024::day_06::patrol_loops(&input, PatrolMethod::Slow),
+        aoc_2024::day_06::patrol_loops(&input, PatrolMethod::Fast)
+    );
+
+    bench.bench_function("Day 06 - Patrol (slow)", |b| {
+        b.iter(|| aoc_2024::day_06::patrol_loops(&input, PatrolMethod::Slow))
+    });
+    bench.bench_function("Day 06 - Patrol (fast)", |b| {
+        b.iter(|| aoc_2024::day_06::patrol_loops(&input, PatrolMethod::Fast))
+    });
+}
+
+criterion::criterion_group!(
+    benches,
+    bench_part_a,
+    bench_part_b,
+    bench_patrol_slow_vs_fast
+);
 criterion::criterion_main!(benches);
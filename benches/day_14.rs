@@ -14,5 +14,17 @@ fn bench_part_b(bench: &mut criterion::Criterion) {
     });
 }
 
-criterion::criterion_group!(benches, bench_part_a, bench_part_b);
+fn bench_part_b_brute_force(bench: &mut criterion::Criterion) {
+    let input: String = util::read_resource("day_14.txt").unwrap();
+    bench.bench_function("Day 14 - Part B (brute force)", |b| {
+        b.iter(|| aoc_2024::day_14::part_b_brute_force(&input))
+    });
+}
+
+criterion::criterion_group!(
+    benches,
+    bench_part_a,
+    bench_part_b,
+    bench_part_b_brute_force
+);
 criterion::criterion_main!(benches);
@@ -4,88 +4,169 @@ struct RunResult {
     name: String,
     solution: String,
     duration: std::time::Duration,
+    /// Only set if parsing and solving were timed separately. The total
+    /// duration then is the sum of the parse and solve durations.
+    parse_duration: Option<std::time::Duration>,
+}
+
+impl RunResult {
+    fn solve_duration(&self) -> Option<std::time::Duration> {
+        self.parse_duration.map(|e| self.duration - e)
+    }
 }
 
 struct Runner {
     invoker: Box<dyn Fn() -> RunResult>,
 }
 
-fn invoke_timed<T>(name: String, invoker: T) -> RunResult
+/// Number of times each solver is invoked. The minimum duration is reported.
+const NUM_RUNS: usize = 11;
+
+/// Combine the results of running all solvers a number of times, keeping the
+/// minimum duration seen for each of them.
+fn min_durations<T>(num_runs: usize, run: T) -> Vec<RunResult>
 where
-    T: Fn() -> String,
+    T: Fn() -> Vec<(String, String, std::time::Duration)>,
 {
-    // Invoke a few times and report median.
-    const NUM_RUNS: usize = 11;
-    let mut duration: std::time::Duration = std::time::Duration::MAX;
+    let mut results: Vec<RunResult> = run()
+        .into_iter()
+        .map(|(name, solution, duration)| RunResult {
+            name,
+            solution,
+            duration,
+            parse_duration: None,
+        })
+        .collect();
+
+    for _ in 1..num_runs {
+        for (result, (_, _, duration)) in results.iter_mut().zip(run()) {
+            result.duration = std::cmp::min(result.duration, duration);
+        }
+    }
+
+    results
+}
+
+fn invoke_timed_split<P, S, T>(name: String, parser: P, solver: S) -> RunResult
+where
+    P: Fn() -> T,
+    S: Fn(T) -> String,
+{
+    // Same as min_durations(), but keeps track of the minimum parse and solve
+    // durations separately.
+    let mut parse_duration = std::time::Duration::MAX;
+    let mut solve_duration = std::time::Duration::MAX;
     let mut solution = String::new();
 
     for _ in 0..NUM_RUNS {
-        let invoke_start = std::time::Instant::now();
-        solution = (invoker)();
-        duration = std::cmp::min(duration, invoke_start.elapsed());
+        let parse_start = std::time::Instant::now();
+        let parsed = (parser)();
+        let solve_start = std::time::Instant::now();
+        solution = (solver)(parsed);
+        let solve_end = std::time::Instant::now();
+
+        parse_duration = std::cmp::min(parse_duration, solve_start - parse_start);
+        solve_duration = std::cmp::min(solve_duration, solve_end - solve_start);
     }
 
-    // Use the minimum seen duration.
     RunResult {
-        name: name,
+        name,
         solution,
-        duration: duration
+        duration: parse_duration + solve_duration,
+        parse_duration: Some(parse_duration),
     }
 }
 
-macro_rules! create_runner {
-    ($day:ident, $part:ident) => {{
+/// Runner timing parsing and solving separately, or None if there's no input.
+macro_rules! create_split_runner {
+    ($day:ident, $part:ident, $parse:ident, $solve:ident) => {{
         let input_file = format!("{}.txt", stringify!($day));
-        let input = util::read_resource(&input_file).unwrap();
-        let name = format!(
-            "{} - {}",
-            heck::AsTitleCase(stringify!($day)),
-            heck::AsTitleCase(stringify!($part))
-        );
-
-        Runner {
-            invoker: Box::new(move || {
-                invoke_timed(name.clone(), || {
-                    format!("{}", aoc_2024::$day::$part(&input))
-                })
-            }),
-        }
+        util::read_resource(&input_file).ok().map(|input| {
+            let name = format!(
+                "{} - {}",
+                heck::AsTitleCase(stringify!($day)),
+                heck::AsTitleCase(stringify!($part))
+            );
+
+            Runner {
+                invoker: Box::new(move || {
+                    invoke_timed_split(
+                        name.clone(),
+                        || aoc_2024::$day::$parse(&input),
+                        |problem| format!("{}", aoc_2024::$day::$solve(&problem)),
+                    )
+                }),
+            }
+        })
     }};
 }
 
-/// Calculate the mean and standard deviation.
-fn mean_and_std<T, U>(data: T) -> Option<(f64, f64)>
-where
-    T: Iterator<Item = U>,
-    U: Into<f64>,
-{
-    // NOTE: This method is numerically unstable. Doesn't matter here though.
-    let mut sum = 0_f64;
-    let mut var = 0_f64;
-    let mut count = 0_usize;
+/// Create a line-by-line diff of two solutions. Matching lines are prefixed
+/// with two spaces, mismatching ones with "- " (expected) and "+ " (actual),
+/// followed by a marker under the first character that differs.
+fn solution_diff(expected: &str, actual: &str) -> String {
+    use itertools::EitherOrBoth;
+    use itertools::Itertools;
 
-    for value in data {
-        count += 1;
+    let mut result = String::new();
 
-        let float: f64 = value.into();
-        sum += float as f64;
-        var += float as f64 * float as f64;
+    for lines in expected.lines().zip_longest(actual.lines()) {
+        let (lhs, rhs) = match lines {
+            EitherOrBoth::Both(lhs, rhs) if lhs == rhs => {
+                result += &format!("  {}\n", lhs);
+                continue;
+            }
+            EitherOrBoth::Both(lhs, rhs) => (lhs, rhs),
+            EitherOrBoth::Left(lhs) => (lhs, ""),
+            EitherOrBoth::Right(rhs) => ("", rhs),
+        };
+
+        let first_mismatch = lhs
+            .chars()
+            .zip_longest(rhs.chars())
+            .position(|e| !matches!(e, EitherOrBoth::Both(l, r) if l == r))
+            .unwrap();
+        result += &format!(
+            "- {}\n+ {}\n  {:>width$}\n",
+            lhs,
+            rhs,
+            "^",
+            width = first_mismatch + 1
+        );
     }
 
-    match count {
-        0 => None,
-        _ => {
-            let mean = sum / count as f64;
-            let std = ((var / count as f64) - mean * mean).sqrt();
+    result
+}
 
-            Some((mean, std))
-        }
-    }
+/// Print a diff between the expected and actual solution of a runner.
+fn print_solution_diff(result: &RunResult, expected: &str) {
+    println!(
+        "{}: mismatching solution\n{}",
+        result.name,
+        solution_diff(expected, &result.solution)
+    );
+}
+
+/// Parse the expected answers, i.e. the contents of resources/expected.txt (see
+/// tests/all_days.rs for its format). Returns them indexed by the name they're
+/// reported under, e.g. "Day 05 - Part A".
+fn parse_expected_answers(contents: &str) -> std::collections::HashMap<String, String> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, ' ');
+            let (day, part, answer) = (fields.next()?, fields.next()?, fields.next()?);
+            let name = format!("{} - {}", heck::AsTitleCase(day), heck::AsTitleCase(part));
+            Some((name, answer.trim().to_string()))
+        })
+        .collect()
 }
 
 fn print_table(results: &[RunResult]) {
     // Grab some statistics for highlighting later on.
-    let (mean, std) = mean_and_std(results.iter().map(|e| e.duration.as_micros() as u32)).unwrap();
+    let (mean, variance) =
+        util::mean_and_variance(results.iter().map(|e| e.duration.as_micros() as u32)).unwrap();
+    let std = variance.sqrt();
 
     // Bunch of helper lambdas.
     let make_cell = |value: String, idx: usize| {
@@ -101,12 +182,25 @@ fn print_table(results: &[RunResult]) {
         }
     };
 
+    // Only show the parse & solve columns if any of the results has them.
+    let split_timing = results.iter().any(|e| e.parse_duration.is_some());
+    let micros_or_empty = |value: Option<std::time::Duration>| {
+        value.map_or(String::new(), |e| e.as_micros().to_string())
+    };
+
     let make_cells = |value: &RunResult, idx: usize| {
-        return [
+        let mut cells = vec![
             make_cell(value.name.clone(), idx),
             make_cell(value.solution.clone(), idx),
             make_cell(value.duration.as_micros().to_string(), idx),
         ];
+
+        if split_timing {
+            cells.push(make_cell(micros_or_empty(value.parse_duration), idx));
+            cells.push(make_cell(micros_or_empty(value.solve_duration()), idx));
+        }
+
+        cells
     };
 
     let highlighted_cells = |value: &RunResult, idx: usize| {
@@ -140,13 +234,16 @@ fn print_table(results: &[RunResult]) {
         .apply_modifier(comfy_table::modifiers::UTF8_ROUND_CORNERS)
         .set_style(comfy_table::TableComponent::BottomLeftCorner, '├')
         .set_style(comfy_table::TableComponent::BottomRightCorner, '┤');
-    table.set_header(["Problem", "Solution", "Time [µs]"]);
+    let mut header = vec!["Problem", "Solution", "Time [µs]"];
+    if split_timing {
+        header.extend(["Parse [µs]", "Solve [µs]"]);
+    }
+    table.set_header(header);
 
     // Add results for all runners.
-    table
-        .column_mut(2)
-        .unwrap()
-        .set_cell_alignment(comfy_table::CellAlignment::Right);
+    for column in table.column_iter_mut().skip(2) {
+        column.set_cell_alignment(comfy_table::CellAlignment::Right);
+    }
 
     for (idx, result) in results.iter().enumerate() {
         table.add_row(highlighted_cells(result, idx));
@@ -172,6 +269,7 @@ fn print_table(results: &[RunResult]) {
         name: String::from("Total"),
         solution: String::default(),
         duration: results.iter().map(|e| e.duration).sum(),
+        parse_duration: None,
     };
     footer_table.add_row(make_cells(&total, table.row_count()));
 
@@ -186,10 +284,9 @@ fn print_table(results: &[RunResult]) {
             .set_constraint(comfy_table::ColumnConstraint::Absolute(total_width));
     }
 
-    footer_table
-        .column_mut(2)
-        .unwrap()
-        .set_cell_alignment(comfy_table::CellAlignment::Right);
+    for column in footer_table.column_iter_mut().skip(2) {
+        column.set_cell_alignment(comfy_table::CellAlignment::Right);
+    }
 
     println!("{}", footer_table);
 }
@@ -197,58 +294,127 @@ fn print_table(results: &[RunResult]) {
 fn main() {
     util::init!();
 
-    let runners: Vec<Runner> = vec![
-        create_runner!(day_01, part_a),
-        create_runner!(day_01, part_b),
-        create_runner!(day_02, part_a),
-        create_runner!(day_02, part_b),
-        create_runner!(day_03, part_a),
-        create_runner!(day_03, part_b),
-        create_runner!(day_04, part_a),
-        create_runner!(day_04, part_b),
-        create_runner!(day_05, part_a),
-        create_runner!(day_05, part_b),
-        create_runner!(day_06, part_a),
-        create_runner!(day_06, part_b),
-        create_runner!(day_07, part_a),
-        create_runner!(day_07, part_b),
-        create_runner!(day_08, part_a),
-        create_runner!(day_08, part_b),
-        create_runner!(day_09, part_a),
-        create_runner!(day_09, part_b),
-        create_runner!(day_10, part_a),
-        create_runner!(day_10, part_b),
-        create_runner!(day_11, part_a),
-        create_runner!(day_11, part_b),
-        create_runner!(day_12, part_a),
-        create_runner!(day_12, part_b),
-        create_runner!(day_13, part_a),
-        create_runner!(day_13, part_b),
-        create_runner!(day_14, part_a),
-        create_runner!(day_14, part_b),
-        create_runner!(day_15, part_a),
-        create_runner!(day_15, part_b),
-        create_runner!(day_16, part_a),
-        create_runner!(day_16, part_b),
-        create_runner!(day_17, part_a),
-        create_runner!(day_17, part_b),
-        create_runner!(day_18, part_a),
-        create_runner!(day_18, part_b),
-        create_runner!(day_19, part_a),
-        create_runner!(day_19, part_b),
-        create_runner!(day_20, part_a),
-        create_runner!(day_20, part_b),
-        create_runner!(day_21, part_a),
-        create_runner!(day_21, part_b),
-        create_runner!(day_22, part_a),
-        create_runner!(day_22, part_b),
-        create_runner!(day_23, part_a),
-        create_runner!(day_23, part_b),
-        create_runner!(day_24, part_a),
-        create_runner!(day_24, part_b),
-        create_runner!(day_25, part_a),
-    ];
-
-    let results: Vec<_> = runners.iter().map(|e| (e.invoker)()).collect();
+    // For some days, parsing and solving can be timed separately.
+    let split_timing = std::env::args().any(|e| e == "--split-timing");
+
+    let mut results = min_durations(NUM_RUNS, aoc_2024::run_all);
+
+    if split_timing {
+        let runners: Vec<Runner> = [
+            create_split_runner!(day_07, part_a, parse, solve_part_a),
+            create_split_runner!(day_07, part_b, parse, solve_part_b),
+            create_split_runner!(day_20, part_a, parse, solve_part_a),
+            create_split_runner!(day_20, part_b, parse, solve_part_b),
+            create_split_runner!(day_22, part_a, parse, solve_part_a),
+            create_split_runner!(day_22, part_b, parse, solve_part_b),
+            create_split_runner!(day_24, part_a, parse, solve_part_a),
+            create_split_runner!(day_24, part_b, parse, solve_part_b),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        for runner in runners {
+            let split_result = (runner.invoker)();
+            if let Some(result) = results.iter_mut().find(|e| e.name == split_result.name) {
+                *result = split_result;
+            }
+        }
+    }
+
+    // Answers can't be shared, so there might not be any to compare against.
+    // Parts without an input don't have a solution either, so skip those.
+    let expected_answers = util::read_resource("expected.txt")
+        .map(|e| parse_expected_answers(&e))
+        .unwrap_or_default();
+    for result in results.iter().filter(|e| !e.solution.is_empty()) {
+        let expected = expected_answers.get(&result.name);
+        if let Some(expected) = expected.filter(|&e| *e != result.solution) {
+            print_solution_diff(result, expected);
+        }
+    }
+
     print_table(&results);
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn split_timing_bookkeeping() {
+        let parse_time = std::time::Duration::from_millis(2);
+        let solve_time = std::time::Duration::from_millis(1);
+
+        let result = crate::invoke_timed_split(
+            String::from("Synthetic"),
+            || {
+                std::thread::sleep(parse_time);
+                21
+            },
+            |parsed| {
+                std::thread::sleep(solve_time);
+                format!("{}", 2 * parsed)
+            },
+        );
+
+        assert_eq!(result.name, "Synthetic");
+        assert_eq!(result.solution, "42");
+
+        let parse_duration = result.parse_duration.unwrap();
+        let solve_duration = result.solve_duration().unwrap();
+        assert!(parse_duration >= parse_time);
+        assert!(solve_duration >= solve_time);
+        assert_eq!(parse_duration + solve_duration, result.duration);
+    }
+
+    #[test]
+    fn solution_diff() {
+        assert_eq!(crate::solution_diff("1,2,3", "1,2,3"), "  1,2,3\n");
+        assert_eq!(
+            crate::solution_diff("4,6,3,5", "4,6,7,5"),
+            "- 4,6,3,5\n+ 4,6,7,5\n      ^\n"
+        );
+        assert_eq!(
+            crate::solution_diff("#.\n.#\n", "#.\n..\n##\n"),
+            "  #.\n- .#\n+ ..\n   ^\n- \n+ ##\n  ^\n"
+        );
+    }
+
+    #[test]
+    fn expected_answers() {
+        let answers = crate::parse_expected_answers("day_05 part_a 143\nday_17 part_a 4,6,3\n\n");
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers["Day 05 - Part A"], "143");
+        assert_eq!(answers["Day 17 - Part A"], "4,6,3");
+    }
+
+    #[test]
+    fn unsplit_timing_bookkeeping() {
+        let num_calls = std::cell::Cell::new(0u64);
+        let results = crate::min_durations(3, || {
+            // Durations of 5, 3 & 4 ms for the first entry, vs 1, 2 & 3 ms for
+            // the second one.
+            num_calls.set(num_calls.get() + 1);
+            let millis = [5, 3, 4][num_calls.get() as usize - 1];
+            vec![
+                (
+                    String::from("First"),
+                    String::from("42"),
+                    std::time::Duration::from_millis(millis),
+                ),
+                (
+                    String::from("Second"),
+                    String::new(),
+                    std::time::Duration::from_millis(num_calls.get()),
+                ),
+            ]
+        });
+
+        assert_eq!(num_calls.get(), 3);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].solution, "42");
+        assert_eq!(results[0].duration, std::time::Duration::from_millis(3));
+        assert_eq!(results[1].duration, std::time::Duration::from_millis(1));
+        assert!(results[0].parse_duration.is_none());
+        assert!(results[0].solve_duration().is_none());
+    }
+}
@@ -12,12 +12,25 @@ trait Report {
 
 impl Report for Vec<i32> {
     fn is_safe(&self) -> bool {
-        let diffs: Vec<_> = self.windows(2).map(|w| w[0] - w[1]).collect();
-        let in_range: bool = diffs.iter().all(|e| e.abs() >= 1 && e.abs() <= 3);
-        let is_monotonic: bool =
-            diffs.iter().all(|e| e.signum() == -1) || diffs.iter().all(|e| e.signum() == 1);
+        // Single pass, without allocating, since is_tolerable() calls this a
+        // lot. The sign of the first difference fixes the direction, all other
+        // differences must have the same one. A difference of 0 is out of range,
+        // so never gets to set the direction.
+        let mut direction = 0;
+        self.windows(2).all(|w| {
+            let diff = w[0] - w[1];
+            if !(1..=3).contains(&diff.abs()) {
+                return false;
+            }
 
-        in_range && is_monotonic
+            match direction {
+                0 => {
+                    direction = diff.signum();
+                    true
+                }
+                _ => diff.signum() == direction,
+            }
+        })
     }
 
     fn is_tolerable(&self) -> bool {
@@ -61,6 +74,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn example_is_safe() {
+        use crate::day_02::Report;
+
+        let reports: [(Vec<i32>, bool); 6] = [
+            (vec![7, 6, 4, 2, 1], true),
+            (vec![1, 2, 7, 8, 9], false),
+            (vec![9, 7, 6, 2, 1], false),
+            (vec![1, 3, 2, 4, 5], false),
+            (vec![8, 6, 4, 4, 1], false),
+            (vec![1, 3, 6, 7, 9], true),
+        ];
+        for (report, expected) in reports.iter() {
+            assert_eq!(report.is_safe(), *expected, "{:?}", report);
+        }
+
+        // Equal adjacent values are never safe, not even at the start.
+        assert!(!vec![3, 3, 4].is_safe());
+        assert!(!vec![3, 4, 4].is_safe());
+
+        // Nothing to compare, so trivially safe.
+        assert!(vec![5].is_safe());
+        assert!(Vec::<i32>::new().is_safe());
+    }
+
     #[test]
     fn example_b() {
         let expected: usize = 4;
@@ -99,56 +99,25 @@ pub fn part_a(input: &str) -> usize {
 pub fn part_b(input: &str) -> usize {
     let word_search: WordSearch = input.parse().unwrap();
 
-    // A cross of needle "MAS" always requires the A to be in the center. So we can
-    // check that first
-    const NEEDLE_LEN: usize = 3;
-    const NEEDLE_MID: u8 = b'A';
-    const NEEDLE_OUTER: [u8; 2] = [b'M', b'S'];
-
-    const OFFSET_OUTERS: [[util::Coord; 2]; 2] = [
-        [
-            // South-east diagonal
-            util::Coord { row: 0, col: 0 },
-            util::Coord { row: 2, col: 2 },
-        ],
-        [
-            // North-east diagonal
-            util::Coord { row: 2, col: 0 },
-            util::Coord { row: 0, col: 2 },
-        ],
-    ];
-    const OFFSET_MID: util::Coord = util::Coord { row: 1, col: 1 };
-
-    itertools::Itertools::cartesian_product(
-        0..(word_search.nrows() - (NEEDLE_LEN - 1)),
-        0..(word_search.ncols() - (NEEDLE_LEN - 1)),
-    )
-    .map(|e| e.into())
-    .filter(|start_coord: &util::Coord| {
-        // Check that all lists of offsets point to elements equal to needle.
-        OFFSET_OUTERS.iter().all(|offset_coords| {
-            let get_elem = |&offset| util::Get::get(&word_search.data.0, &(*start_coord + offset));
-
-            if get_elem(&OFFSET_MID).is_some_and(|e| *e != NEEDLE_MID) {
-                // NOTE: This rechecked for each offset list, which is a bit redundant.
-                false
-            } else {
-                let values = offset_coords.iter().map(|e| get_elem(e));
-
-                if values.clone().any(|e| e.is_none()) {
-                    // All coordinates must fall within the matrix.
-                    false
-                } else {
-                    // Values must match either forward or backward.
-                    std::iter::zip(values.clone(), NEEDLE_OUTER.iter())
-                        .all(|(value, needle)| value.unwrap() == needle)
-                        || std::iter::zip(values, NEEDLE_OUTER.iter().rev())
-                            .all(|(value, needle)| value.unwrap() == needle)
-                }
-            }
+    // A cross of needle "MAS" always has the A in the center, and each of the
+    // diagonals can read either forward or backward. That makes four stencils,
+    // of which at most one can match at any position. Check the A first, since
+    // that rules out most positions.
+    const NEEDLE_OUTER: [(u8, u8); 2] = [(b'M', b'S'), (b'S', b'M')];
+    let offset = |row, col| util::Coord { row, col };
+
+    itertools::Itertools::cartesian_product(NEEDLE_OUTER.iter(), NEEDLE_OUTER.iter())
+        .map(|(&(north_west, south_east), &(north_east, south_west))| {
+            let stencil = [
+                (offset(0, 0), b'A'),
+                (offset(-1, -1), north_west),
+                (offset(1, 1), south_east),
+                (offset(-1, 1), north_east),
+                (offset(1, -1), south_west),
+            ];
+            util::count_stencil(&word_search.data.0, &stencil)
         })
-    })
-    .count()
+        .sum()
 }
 
 #[cfg(test)]
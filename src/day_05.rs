@@ -81,24 +81,21 @@ impl std::str::FromStr for Problem {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut result = Problem::new();
-        let mut lines = s.lines();
-
-        lines
-            .by_ref()
-            .take_while(|e| !e.is_empty())
-            .for_each(|line| {
-                let (before, after) = line
-                    .split("|")
-                    .map(|e| e.parse().unwrap())
-                    .next_tuple()
-                    .unwrap();
-
-                // Create HashMap entry if it doesn't exist.
-                result.print_after.entry(after).or_default().push(before);
-            });
-
-        result.updates = lines
-            .skip_while(|e| e.is_empty())
+        let (rules, updates) = util::split_sections(s);
+
+        rules.lines().for_each(|line| {
+            let (before, after) = line
+                .split("|")
+                .map(|e| e.parse().unwrap())
+                .next_tuple()
+                .unwrap();
+
+            // Create HashMap entry if it doesn't exist.
+            result.print_after.entry(after).or_default().push(before);
+        });
+
+        result.updates = updates
+            .lines()
             .map(|line| line.split(",").map(|e| e.parse().unwrap()).collect())
             .collect();
 
@@ -109,22 +106,22 @@ impl std::str::FromStr for Problem {
 pub fn part_a(input: &str) -> usize {
     let problem: Problem = input.parse().unwrap();
 
-    problem
+    let valid_updates = problem
         .updates
         .iter()
-        .filter(|update| problem.is_valid_update(update).is_ok())
-        .fold(0, |sum, update| sum + update[update.len() / 2] as usize)
+        .filter(|update| problem.is_valid_update(update).is_ok());
+    util::sum_middles(valid_updates) as usize
 }
 
 pub fn part_b(input: &str) -> usize {
     let problem: Problem = input.parse().unwrap();
 
-    problem
+    let fixed_updates = problem
         .updates
         .iter()
         .filter(|update| problem.is_valid_update(update).is_err())
-        .map(|update| problem.make_valid_update(update))
-        .fold(0, |sum, update| sum + update[update.len() / 2] as usize)
+        .map(|update| problem.make_valid_update(update));
+    util::sum_middles(fixed_updates) as usize
 }
 
 #[cfg(test)]
@@ -1,28 +1,44 @@
 use nalgebra as na;
 use rayon::prelude::*;
 
+/// Which way the guard turns when it walks into an obstruction.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TurnRule {
+    #[default]
+    Clockwise,
+    CounterClockwise,
+}
+
 trait DirectionUtils {
     const NUM_DIRECTIONS: usize;
 
-    fn turn(self) -> util::Direction;
+    fn turn(self, rule: TurnRule) -> util::Direction;
+    fn reverse(self) -> util::Direction;
     fn index(self) -> usize;
     fn from(index: usize) -> util::Direction;
-    fn mask(self) -> u8;
 }
 
 impl DirectionUtils for util::Direction {
     const NUM_DIRECTIONS: usize = 4;
 
-    fn turn(self) -> util::Direction {
-        match self {
-            util::Direction::North => util::Direction::East,
-            util::Direction::East => util::Direction::South,
-            util::Direction::South => util::Direction::West,
-            util::Direction::West => util::Direction::North,
+    fn turn(self, rule: TurnRule) -> util::Direction {
+        match (self, rule) {
+            (util::Direction::North, TurnRule::Clockwise) => util::Direction::East,
+            (util::Direction::East, TurnRule::Clockwise) => util::Direction::South,
+            (util::Direction::South, TurnRule::Clockwise) => util::Direction::West,
+            (util::Direction::West, TurnRule::Clockwise) => util::Direction::North,
+            (util::Direction::North, TurnRule::CounterClockwise) => util::Direction::West,
+            (util::Direction::East, TurnRule::CounterClockwise) => util::Direction::North,
+            (util::Direction::South, TurnRule::CounterClockwise) => util::Direction::East,
+            (util::Direction::West, TurnRule::CounterClockwise) => util::Direction::South,
             _ => unreachable!(),
         }
     }
 
+    fn reverse(self) -> util::Direction {
+        self.turn(TurnRule::Clockwise).turn(TurnRule::Clockwise)
+    }
+
     fn index(self) -> usize {
         match self {
             util::Direction::North => 0,
@@ -42,16 +58,6 @@ impl DirectionUtils for util::Direction {
             _ => unreachable!(),
         }
     }
-
-    fn mask(self) -> u8 {
-        match self {
-            util::Direction::North => 1 << 0,
-            util::Direction::East => 1 << 1,
-            util::Direction::South => 1 << 2,
-            util::Direction::West => 1 << 3,
-            _ => unreachable!(),
-        }
-    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -61,29 +67,28 @@ struct Guard {
 }
 
 impl std::str::FromStr for Guard {
-    type Err = std::string::ParseError;
+    type Err = util::InputError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let rows = s.lines().count();
-        let cols = s.lines().next().unwrap().len();
+        let (_, cols) = util::grid_dimensions(s)?;
 
-        const GUARD_SYMBOL: u8 = b'^';
-        let (index, _) = s
+        // The guard's glyph is an arrow pointing in its initial direction.
+        let (index, dir) = s
             .lines()
             .flat_map(|e| e.as_bytes().iter())
             .enumerate()
-            .find(|(_, &e)| e == GUARD_SYMBOL)
-            .unwrap();
+            .find_map(|(idx, &e)| util::Direction::from_ascii(e).map(|dir| (idx, dir)))
+            .ok_or(util::InputError::MissingMarker("guard"))?;
 
         Ok(Self {
-            pos: (index / cols, index % rows).into(),
-            dir: util::Direction::North,
+            pos: (index / cols, index % cols).into(),
+            dir,
         })
     }
 }
 
 #[derive(Clone, Debug)]
-struct StepTable {
+pub struct StepTable {
     /// This table stores for each direction the number of steps to take until
     /// either an obstacle is reached, or we're out of bounds.
     steps_to_obstruction: [na::DMatrix<u8>; <util::Direction as DirectionUtils>::NUM_DIRECTIONS],
@@ -150,7 +155,9 @@ impl StepTable {
         result
     }
 
-    fn add_obstruction(&mut self, pos: util::Coord) {
+    /// Place an obstruction on the given (unobstructed) square, and update the
+    /// step counts of all squares from which it would be the first one hit.
+    pub fn add_obstruction(&mut self, pos: util::Coord) {
         // Update all jump values on squares between new obstacle and previous one.
         // Step counts going forward in the "active" direction don't need to be
         // updated, because each cell already contains the number of steps to
@@ -164,7 +171,7 @@ impl StepTable {
                 // Get the number of steps to go in the opposite direction from the
                 // square just before the one that is getting an obstruction added.
                 let dir = <util::Direction as DirectionUtils>::from(dir_idx);
-                let backward_dir = dir.turn().turn();
+                let backward_dir = dir.reverse();
                 let backward_step: util::Coord = backward_dir.into();
 
                 // The previous position indicates how many steps must be taken to
@@ -195,7 +202,7 @@ impl StepTable {
             // Get the number of steps to go in the opposite direction from the
             // square just before the one that is getting an obstruction added.
             let dir = <util::Direction as DirectionUtils>::from(dir_idx);
-            let backward_dir = dir.turn().turn();
+            let backward_dir = dir.reverse();
             let backward_step: util::Coord = backward_dir.into();
 
             // Update all squares between the previous obstacle and the new
@@ -224,7 +231,9 @@ impl StepTable {
         }
     }
 
-    fn remove_obstruction(&mut self, pos: util::Coord) {
+    /// Inverse of add_obstruction(), i.e. afterwards the table is exactly the
+    /// same as before the obstruction was added.
+    pub fn remove_obstruction(&mut self, pos: util::Coord) {
         // Update all jump values on squares between new obstacle and previous one.
         // Step counts going forward in the "active" direction don't need to be
         // updated, because each cell already contains the number of steps to
@@ -239,7 +248,7 @@ impl StepTable {
                 // Get the number of steps to go in the opposite direction from the
                 // square just before the one that is getting an obstruction removed.
                 let dir = <util::Direction as DirectionUtils>::from(dir_idx);
-                let backward_dir = dir.turn().turn();
+                let backward_dir = dir.reverse();
 
                 let step: util::Coord = dir.into();
                 let backward_step: util::Coord = backward_dir.into();
@@ -289,7 +298,7 @@ impl StepTable {
             // Update all squares between (and including) the newly unobstructed one and
             // the previous obstacle going backwards.
             let dir = <util::Direction as DirectionUtils>::from(dir_idx);
-            let backward_dir = dir.turn().turn();
+            let backward_dir = dir.reverse();
             let backward_step: util::Coord = backward_dir.into();
             let (cells_to_update, steps_offset) = update_info[dir_idx];
 
@@ -311,25 +320,27 @@ impl StepTable {
         }
     }
 
-    fn remaining_steps(&self, pos: util::Coord, dir: util::Direction) -> u8 {
+    /// Returns the number of steps the guard can take from the given square in
+    /// the given direction, before either standing in front of an obstruction
+    /// or having left the room.
+    pub fn remaining_steps(&self, pos: util::Coord, dir: util::Direction) -> u8 {
         let result = self.steps_to_obstruction[dir.index()][pos.as_pair()];
         log::trace!("Steps going {:?} from {:?}: {}", dir, pos, result);
         assert!(result != Self::MARKER);
         result
     }
 
-    fn is_obstructed(&self, pos: util::Coord) -> bool {
+    pub fn is_obstructed(&self, pos: util::Coord) -> bool {
         // Doesn't matter which direction we check.
         self.steps_to_obstruction[0][pos.as_pair()] == Self::MARKER
     }
 }
 
 impl std::str::FromStr for StepTable {
-    type Err = std::string::ParseError;
+    type Err = util::InputError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let rows = s.lines().count();
-        let cols = s.lines().next().unwrap().len();
+        let (rows, cols) = util::grid_dimensions(s)?;
 
         let mut result = StepTable::new(rows, cols);
 
@@ -364,31 +375,63 @@ struct Problem {
     step_table: StepTable,
     guard: Guard,
     room_size: util::Coord,
+    turn_rule: TurnRule,
 }
 
 impl std::str::FromStr for Problem {
-    type Err = std::string::ParseError;
+    type Err = util::InputError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let rows = s.lines().count();
-        let cols = s.lines().next().unwrap().len();
+        let (rows, cols) = util::grid_dimensions(s)?;
 
         Ok(Self {
-            step_table: s.parse().unwrap(),
-            guard: s.parse().unwrap(),
+            step_table: s.parse()?,
+            guard: s.parse()?,
             room_size: (rows, cols).into(),
+            turn_rule: TurnRule::default(),
         })
     }
 }
 
 struct Patrol {
-    visited: na::DMatrix<u8>,
+    visited: na::DMatrix<util::DirectionSet>,
+    is_loop: bool,
+    is_truncated: bool,
 }
 
 impl Patrol {
     fn new(room_size: util::Coord) -> Patrol {
         Patrol {
-            visited: na::DMatrix::zeros(room_size.row as usize, room_size.col as usize),
+            visited: na::DMatrix::from_element(
+                room_size.row as usize,
+                room_size.col as usize,
+                util::DirectionSet::new(),
+            ),
+            is_loop: false,
+            is_truncated: false,
+        }
+    }
+}
+
+/// Result of simulating the guard's patrol until it either leaves the lab,
+/// walks in a loop, or runs out of allowed steps. All variants contain all
+/// distinct squares visited by the guard (including its starting square), in
+/// row-major order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatrolOutcome {
+    Exited(Vec<util::Coord>),
+    Looped(Vec<util::Coord>),
+    /// The step limit was reached before the guard left the lab or a loop was
+    /// detected.
+    Indeterminate(Vec<util::Coord>),
+}
+
+impl PatrolOutcome {
+    pub fn visited(&self) -> &[util::Coord] {
+        match self {
+            PatrolOutcome::Exited(visited)
+            | PatrolOutcome::Looped(visited)
+            | PatrolOutcome::Indeterminate(visited) => visited,
         }
     }
 }
@@ -399,7 +442,7 @@ impl Problem {
             StepTable::MARKER => unreachable!(), // Somehow ended up on an obstruction.
             0 => {
                 // No more steps allowed in this direction, just turn.
-                guard.dir = guard.dir.turn();
+                guard.dir = guard.dir.turn(self.turn_rule);
             }
             _ => {
                 // Take a single step, so we can properly track all the visited squares.
@@ -420,11 +463,22 @@ impl Problem {
     /// squares visited by the guard, in the direction it was visited. I.e. it's
     /// possible the same square is visited from multiple directions.
     fn patrol_slow(&self) -> Patrol {
+        self._patrol_slow(usize::MAX)
+    }
+
+    /// Same as patrol_slow(), but gives up after max_steps steps (where turning
+    /// in place also counts as a step).
+    fn _patrol_slow(&self, max_steps: usize) -> Patrol {
         let mut result = Patrol::new(self.room_size);
         let mut guard = self.guard;
 
-        // Iterate until guard loops or goes out of bounds.
-        loop {
+        // Iterate until guard loops, goes out of bounds, or runs out of steps.
+        for step in 0.. {
+            if step == max_steps {
+                result.is_truncated = true;
+                break;
+            }
+
             // Take a step in the current direction.
             guard = match self.advance_guard_slow(guard) {
                 None => break,
@@ -432,15 +486,33 @@ impl Problem {
             };
 
             let square_visited = unsafe { result.visited.get_unchecked_mut(guard.pos.as_pair()) };
-            if (*square_visited & guard.dir.mask()) != 0 {
+            if !square_visited.insert(guard.dir) {
+                result.is_loop = true;
                 break; // Stop, guard was here before.
             }
-            *square_visited |= guard.dir.mask();
         }
 
         result
     }
 
+    /// Simulate the guard patrolling the lab square by square, for at most
+    /// max_steps steps. This bounds the amount of work on huge maps, where the
+    /// guard might wander around for a long time before leaving or looping.
+    fn patrol_bounded(&self, max_steps: usize) -> PatrolOutcome {
+        let patrol = self._patrol_slow(max_steps);
+
+        let visited: Vec<_> = util::iter_row_major(&patrol.visited)
+            .filter(|&(pos, was_visited)| !was_visited.is_empty() || pos == self.guard.pos)
+            .map(|(pos, _)| pos)
+            .collect();
+
+        match (patrol.is_loop, patrol.is_truncated) {
+            (true, _) => PatrolOutcome::Looped(visited),
+            (false, true) => PatrolOutcome::Indeterminate(visited),
+            (false, false) => PatrolOutcome::Exited(visited),
+        }
+    }
+
     fn advance_guard_fast(&self, mut guard: Guard, step_table: &StepTable) -> Option<Guard> {
         match step_table.remaining_steps(guard.pos, guard.dir) {
             StepTable::MARKER => unreachable!(), // Somehow ended up on an obstruction.
@@ -449,7 +521,7 @@ impl Problem {
                 // preparation for the next jump. Note that the jump can
                 // have a length of zero.
                 guard.pos += steps * util::Coord::from(guard.dir);
-                guard.dir = guard.dir.turn();
+                guard.dir = guard.dir.turn(self.turn_rule);
 
                 match guard.pos.bounded_by(&self.room_size) {
                     true => Some(guard),
@@ -493,6 +565,50 @@ impl Problem {
     }
 }
 
+/// Simulate the guard's patrol on the given map, square by square.
+pub fn simulate_patrol(input: &str) -> PatrolOutcome {
+    simulate_patrol_with_turn_rule(input, TurnRule::default())
+}
+
+/// Same as simulate_patrol(), but the guard turns according to the given rule
+/// when it walks into an obstruction.
+pub fn simulate_patrol_with_turn_rule(input: &str, turn_rule: TurnRule) -> PatrolOutcome {
+    let mut problem: Problem = input.parse().unwrap();
+    problem.turn_rule = turn_rule;
+    problem.patrol_bounded(usize::MAX)
+}
+
+/// Same as simulate_patrol(), but stops after at most max_steps steps, in
+/// which case PatrolOutcome::Indeterminate is returned.
+pub fn simulate_patrol_bounded(input: &str, max_steps: usize) -> PatrolOutcome {
+    let problem: Problem = input.parse().unwrap();
+    problem.patrol_bounded(max_steps)
+}
+
+/// Which simulation to use to check whether the guard's patrol is a loop.
+#[derive(Copy, Clone, Debug)]
+pub enum PatrolMethod {
+    /// Step square by square, tracking all visited squares.
+    Slow,
+    /// Jump between obstructions using the step table.
+    Fast,
+}
+
+/// Check whether the guard walks in a loop, using the given simulation.
+pub fn patrol_loops(input: &str, method: PatrolMethod) -> bool {
+    let problem: Problem = input.parse().unwrap();
+    match method {
+        PatrolMethod::Slow => problem.patrol_slow().is_loop,
+        PatrolMethod::Fast => problem.patrol_fast(&problem.step_table),
+    }
+}
+
+/// Only runs the parser on the input, without solving anything. Returns an
+/// error describing the problem if the input is malformed.
+pub fn parse(input: &str) -> Result<(), util::InputError> {
+    input.parse::<Problem>().map(|_| ())
+}
+
 pub fn part_a(input: &str) -> usize {
     let problem: Problem = input.parse().unwrap();
 
@@ -501,7 +617,7 @@ pub fn part_a(input: &str) -> usize {
         .visited
         .as_slice()
         .par_iter()
-        .filter(|&&was_visited| was_visited != 0)
+        .filter(|was_visited| !was_visited.is_empty())
         .count()
 }
 
@@ -510,7 +626,9 @@ pub fn part_b(input: &str) -> usize {
 
     // Find all squares visited during the original patrol.
     let orig_patrol = problem.patrol_slow();
-    let num_workers: usize = std::thread::available_parallelism().unwrap().get();
+    // NOTE: When called from within a dedicated thread pool (see
+    // part_b_with_threads()), this returns that pool's number of threads.
+    let num_workers: usize = rayon::current_num_threads();
 
     let patrol_coords: Vec<_> = orig_patrol
         .visited
@@ -519,9 +637,9 @@ pub fn part_b(input: &str) -> usize {
         .with_min_len(orig_patrol.visited.len().div_ceil(num_workers))
         .enumerate()
         .filter_map(|(square_idx, was_visited)| {
-            match was_visited {
-                0 => None,
-                _ => {
+            match was_visited.is_empty() {
+                true => None,
+                false => {
                     // WARN: nalgebra's iter() is column-major! So must adapt
                     // coord calculation accordingly.
                     let pos = util::Coord::from_column_major_index(
@@ -565,6 +683,16 @@ pub fn part_b(input: &str) -> usize {
         .sum()
 }
 
+/// Same as part_b(), but runs on a dedicated thread pool with the given number
+/// of threads, instead of on rayon's global one.
+pub fn part_b_with_threads(input: &str, num_threads: usize) -> usize {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .build()
+        .unwrap()
+        .install(|| part_b(input))
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -588,4 +716,202 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn example_b_with_threads() {
+        util::run_test(|| {
+            let expected: usize = 6;
+            let input = util::read_resource("example_06.txt").unwrap();
+            for num_threads in [1, 4] {
+                assert_eq!(
+                    crate::day_06::part_b_with_threads(&input, num_threads),
+                    expected
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn obstruction_toggle_round_trip() {
+        util::run_test(|| {
+            let input = util::read_resource("example_06.txt").unwrap();
+            let mut step_table: crate::day_06::StepTable = input.parse().unwrap();
+            let (rows, cols) = util::grid_dimensions(&input).unwrap();
+
+            // Simple xorshift, so the test is deterministic without pulling in
+            // a dependency.
+            let mut rng_state: u64 = 0x2024_0606;
+            let mut random_pos = |step_table: &crate::day_06::StepTable| loop {
+                rng_state ^= rng_state << 13;
+                rng_state ^= rng_state >> 7;
+                rng_state ^= rng_state << 17;
+                let idx = (rng_state % (rows * cols) as u64) as usize;
+                let pos = util::Coord::from_row_major_index(idx, rows, cols);
+                if !step_table.is_obstructed(pos) {
+                    break pos;
+                }
+            };
+
+            for _ in 0..200 {
+                let orig = step_table.clone();
+
+                // Add a few obstructions on top of each other, then remove them
+                // again in reverse order.
+                let mut added = Vec::new();
+                for _ in 0..3 {
+                    let pos = random_pos(&step_table);
+                    step_table.add_obstruction(pos);
+                    assert!(step_table.is_obstructed(pos));
+                    added.push(pos);
+                }
+                for pos in added.into_iter().rev() {
+                    step_table.remove_obstruction(pos);
+                    assert!(!step_table.is_obstructed(pos));
+                }
+
+                assert_eq!(step_table.steps_to_obstruction, orig.steps_to_obstruction);
+            }
+        });
+    }
+
+    #[test]
+    fn example_simulate_patrol() {
+        util::run_test(|| {
+            let outcome =
+                crate::day_06::simulate_patrol(&util::read_resource("example_06.txt").unwrap());
+            assert!(matches!(outcome, crate::day_06::PatrolOutcome::Exited(_)));
+            assert_eq!(outcome.visited().len(), 41);
+        });
+    }
+
+    #[test]
+    fn bounded_patrol() {
+        util::run_test(|| {
+            use crate::day_06::PatrolOutcome;
+
+            let input = util::read_resource("example_06.txt").unwrap();
+            let outcome = crate::day_06::simulate_patrol_bounded(&input, 3);
+            assert!(matches!(outcome, PatrolOutcome::Indeterminate(_)));
+            assert_eq!(outcome.visited().len(), 4);
+
+            let outcome = crate::day_06::simulate_patrol_bounded(&input, 1000);
+            assert!(matches!(outcome, PatrolOutcome::Exited(_)));
+            assert_eq!(outcome.visited().len(), 41);
+
+            // A loop is still detected, as long as the limit isn't hit first.
+            let input = ".#...\n....#\n#^...\n...#.\n";
+            let outcome = crate::day_06::simulate_patrol_bounded(input, 2);
+            assert!(matches!(outcome, PatrolOutcome::Indeterminate(_)));
+            let outcome = crate::day_06::simulate_patrol_bounded(input, 100);
+            assert!(matches!(outcome, PatrolOutcome::Looped(_)));
+        });
+    }
+
+    #[test]
+    fn simulate_patrol_loop() {
+        util::run_test(|| {
+            // The guard keeps walking around a 2x3 rectangle of squares.
+            let input = ".#...\n....#\n#^...\n...#.\n";
+            let outcome = crate::day_06::simulate_patrol(input);
+            let expected: Vec<util::Coord> = vec![
+                (1, 1).into(),
+                (1, 2).into(),
+                (1, 3).into(),
+                (2, 1).into(),
+                (2, 2).into(),
+                (2, 3).into(),
+            ];
+            assert_eq!(outcome, crate::day_06::PatrolOutcome::Looped(expected));
+        });
+    }
+
+    #[test]
+    fn patrol_methods_agree() {
+        util::run_test(|| {
+            use crate::day_06::PatrolMethod;
+
+            let looping = ".#...\n....#\n#^...\n...#.\n";
+            let exiting = util::read_resource("example_06.txt").unwrap();
+            for method in [PatrolMethod::Slow, PatrolMethod::Fast] {
+                assert!(crate::day_06::patrol_loops(looping, method));
+                assert!(!crate::day_06::patrol_loops(&exiting, method));
+            }
+        });
+    }
+
+    #[test]
+    fn counter_clockwise_guard() {
+        util::run_test(|| {
+            use crate::day_06::{PatrolOutcome, TurnRule};
+
+            // Mirror image of the map in simulate_patrol_loop(), so a guard
+            // turning left walks in a loop, while one turning right leaves.
+            let input = "...#.\n#....\n...^#\n.#...\n";
+            let expected: Vec<util::Coord> = vec![
+                (1, 1).into(),
+                (1, 2).into(),
+                (1, 3).into(),
+                (2, 1).into(),
+                (2, 2).into(),
+                (2, 3).into(),
+            ];
+            assert_eq!(
+                crate::day_06::simulate_patrol_with_turn_rule(input, TurnRule::CounterClockwise),
+                PatrolOutcome::Looped(expected)
+            );
+
+            let outcome = crate::day_06::simulate_patrol(input);
+            assert!(matches!(outcome, PatrolOutcome::Exited(_)));
+            assert_eq!(
+                outcome.visited(),
+                [(1, 3).into(), (1, 4).into(), (2, 3).into()]
+            );
+
+            // The jumping patrol agrees with the square by square one.
+            let mut problem: crate::day_06::Problem = input.parse().unwrap();
+            assert!(!problem.patrol_fast(&problem.step_table));
+            problem.turn_rule = TurnRule::CounterClockwise;
+            assert!(problem.patrol_fast(&problem.step_table));
+        });
+    }
+
+    #[test]
+    fn guard_facing_east() {
+        util::run_test(|| {
+            let input = "....\n.>..\n....\n";
+            let problem: crate::day_06::Problem = input.parse().unwrap();
+            assert_eq!(problem.guard.pos, util::Coord { row: 1, col: 1 });
+            assert_eq!(problem.guard.dir, util::Direction::East);
+
+            // Without obstacles the guard walks straight out of the lab's east side.
+            let patrol = problem.patrol_slow();
+            let mut east = util::DirectionSet::new();
+            east.insert(util::Direction::East);
+            assert_eq!(patrol.visited[(1, 2)], east);
+            assert_eq!(patrol.visited[(1, 3)], east);
+            assert_eq!(patrol.visited.iter().filter(|e| !e.is_empty()).count(), 2);
+        });
+    }
+
+    #[test]
+    fn no_guard() {
+        util::run_test(|| {
+            let input = "....\n.#..\n....\n";
+            let problem = input.parse::<crate::day_06::Problem>();
+            let err = problem.unwrap_err();
+            assert_eq!(err, util::InputError::MissingMarker("guard"));
+            assert_eq!(err.to_string(), "no guard found in map");
+        });
+    }
+
+    #[test]
+    fn ragged_map() {
+        util::run_test(|| {
+            let input = "....\n.^.\n....\n";
+            assert!(matches!(
+                input.parse::<crate::day_06::Problem>(),
+                Err(util::InputError::RaggedGrid { row: 1, .. })
+            ));
+        });
+    }
 }
@@ -9,25 +9,37 @@ struct Equation {
     values: SmallVec<[u16; NUM_ELEMENTS]>,
 }
 
+/// An operator which can be solved for its left-hand side, i.e. given the
+/// result of applying it and its right-hand side, reverse_eval() returns the
+/// left-hand side. Returns None if there's no such (non-negative) left-hand
+/// side, which prunes that branch of the search.
+pub trait ReverseOp: Sync {
+    fn reverse_eval(&self, target: u64, rhs: u64) -> Option<u64>;
+}
+
+impl<T: ReverseOp + ?Sized> ReverseOp for &T {
+    fn reverse_eval(&self, target: u64, rhs: u64) -> Option<u64> {
+        (**self).reverse_eval(target, rhs)
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
-enum Operator {
+pub enum Operator {
     Add,
     Mult,
     Concat,
 }
 
-impl Operator {
-    fn reverse_eval(self, lhs: u64, rhs: u64) -> Option<u64> {
+impl ReverseOp for Operator {
+    fn reverse_eval(&self, lhs: u64, rhs: u64) -> Option<u64> {
         match self {
-            Operator::Add => {
-                assert!(lhs >= rhs); // Guaranteed by _solve_reverse() function.
-                Some(lhs - rhs)
-            }
+            Operator::Add => lhs.checked_sub(rhs),
             Operator::Mult => match lhs % rhs {
                 0 => Some(lhs / rhs),
                 _ => None,
             },
             Operator::Concat => {
+                // If lhs is smaller than rhs, then the modulo below can't match.
                 const DIVISORS: [u64; 3] = [10, 100, 1000];
 
                 let divisor_idx = util::digit_width_base10(rhs) - 1;
@@ -63,12 +75,14 @@ impl std::str::FromStr for Equation {
 }
 
 impl Equation {
-    fn _solve_reversed(&self, target: u64, values: &[u16], operators: &[Operator]) -> bool {
+    fn _solve_reversed<Op: ReverseOp>(
+        &self,
+        target: u64,
+        values: &[u16],
+        operators: &[Op],
+    ) -> bool {
         if values.len() == 1 {
             return values[0] as u64 == target;
-        } else if target < values[values.len() - 1] as u64 {
-            // If target is smaller than a value, then this can never solve.
-            return false;
         }
 
         for op in operators.iter() {
@@ -85,7 +99,7 @@ impl Equation {
         false
     }
 
-    fn solvable(&self, operators: &[Operator]) -> bool {
+    fn solvable<Op: ReverseOp>(&self, operators: &[Op]) -> bool {
         // Solve from "back to front". I.e. start with the expected value and
         // work back through the list of values until the first one is reached
         // and matches the remaining expected value.
@@ -93,26 +107,47 @@ impl Equation {
     }
 }
 
-fn solve(input: &str, operators: &[Operator]) -> u64 {
+#[derive(Debug)]
+pub struct Problem {
+    equations: Vec<Equation>,
+}
+
+/// Parse the input, such that it can be solved separately afterwards.
+pub fn parse(input: &str) -> Problem {
     // Collect into vector to allow rayon to efficiently split objects across
     // its workers.
-    let equations: Vec<_> = input.lines().map(|e| e.parse().unwrap()).collect();
+    Problem {
+        equations: input.lines().map(|e| e.parse().unwrap()).collect(),
+    }
+}
 
-    equations
+/// Sums the targets of all equations which can be made true by combining their
+/// values from left to right with any of the given operators.
+pub fn solve<Op: ReverseOp>(problem: &Problem, operators: &[Op]) -> u64 {
+    problem
+        .equations
         .par_iter()
         .filter(|eq: &&Equation| eq.solvable(operators))
         .map(|e| e.target)
         .sum()
 }
 
-pub fn part_a(input: &str) -> u64 {
+pub fn solve_part_a(problem: &Problem) -> u64 {
     let operators = [Operator::Mult, Operator::Add];
-    solve(input, &operators)
+    solve(problem, &operators)
 }
 
-pub fn part_b(input: &str) -> u64 {
+pub fn solve_part_b(problem: &Problem) -> u64 {
     let operators = [Operator::Concat, Operator::Mult, Operator::Add];
-    solve(input, &operators)
+    solve(problem, &operators)
+}
+
+pub fn part_a(input: &str) -> u64 {
+    solve_part_a(&parse(input))
+}
+
+pub fn part_b(input: &str) -> u64 {
+    solve_part_b(&parse(input))
 }
 
 #[cfg(test)]
@@ -138,4 +173,31 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn custom_operator() {
+        use crate::day_07::{Operator, ReverseOp};
+
+        struct Subtract;
+
+        impl ReverseOp for Subtract {
+            fn reverse_eval(&self, target: u64, rhs: u64) -> Option<u64> {
+                target.checked_add(rhs)
+            }
+        }
+
+        util::run_test(|| {
+            // 10 - 3 - 5 = 2 and 10 - 3 + 5 = 12, but nothing reaches 17.
+            let problem = crate::day_07::parse("2: 10 3 5\n12: 10 3 5\n17: 10 3 5\n");
+            assert_eq!(crate::day_07::solve(&problem, &[Subtract]), 2);
+            assert_eq!(
+                crate::day_07::solve(&problem, &[&Subtract as &dyn ReverseOp, &Operator::Add]),
+                2 + 12
+            );
+            assert_eq!(
+                crate::day_07::solve(&problem, &[Operator::Mult, Operator::Add]),
+                0
+            );
+        });
+    }
 }
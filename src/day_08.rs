@@ -1,6 +1,6 @@
 #[derive(Debug)]
 struct Problem {
-    antennas: std::collections::HashMap<char, Vec<util::Coord>>,
+    antennas: std::collections::HashMap<u8, Vec<util::Coord>>,
     city_bounds: util::Coord,
 }
 
@@ -8,25 +8,10 @@ impl std::str::FromStr for Problem {
     type Err = std::string::ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut result = Problem {
-            antennas: std::collections::HashMap::new(),
+        Ok(Problem {
+            antennas: util::marker_positions(s, |e| e.is_ascii_alphanumeric()),
             city_bounds: (s.lines().count(), s.lines().next().unwrap().len()).into(),
-        };
-
-        for (row, line) in s.lines().enumerate() {
-            for (col, value) in line.chars().enumerate() {
-                match value {
-                    'A'..='Z' | 'a'..='z' | '0'..='9' => result
-                        .antennas
-                        .entry(value)
-                        .or_insert(Vec::new())
-                        .push((row, col).into()),
-                    _ => (),
-                }
-            }
-        }
-
-        Ok(result)
+        })
     }
 }
 
@@ -50,40 +35,88 @@ pub fn part_a(input: &str) -> usize {
     antinodes.len()
 }
 
-pub fn part_b(input: &str) -> usize {
+impl Problem {
+    /// All antinodes within the city of a single pair of antennas, for the
+    /// harmonics from min_harmonic up to max_harmonic (both inclusive).
+    fn pair_antinodes(
+        &self,
+        coord_pair: [&util::Coord; 2],
+        min_harmonic: usize,
+        max_harmonic: usize,
+    ) -> impl Iterator<Item = util::Coord> + '_ {
+        let (coord_min, coord_max) = util::coord_minmax(coord_pair.iter().map(|&&e| e)).unwrap();
+        let offset = coord_max - coord_min;
+        let harmonics = move || (min_harmonic..=max_harmonic).map(|e| e as isize);
+
+        let forward_iter = harmonics()
+            .map(move |step| coord_max + step * offset)
+            .take_while(|coord| !coord.has_negatives() && coord.bounded_by(&self.city_bounds));
+
+        let backward_iter = harmonics()
+            .map(move |step| coord_min - step * offset)
+            .take_while(|coord| !coord.has_negatives() && coord.bounded_by(&self.city_bounds));
+
+        forward_iter.chain(backward_iter)
+    }
+}
+
+/// Count the unique antinodes within the city, only taking into account the
+/// harmonics from min_harmonic up to max_harmonic (both inclusive). The k-th
+/// harmonic of a pair of antennas lies k times the distance between them
+/// beyond either antenna. I.e. the 0th harmonic are the antennas themselves,
+/// part A only looks at the 1st harmonic and part B looks at all of them. Use
+/// usize::MAX as max_harmonic to not limit the harmonics.
+pub fn resonant_antinodes(input: &str, min_harmonic: usize, max_harmonic: usize) -> usize {
     let problem: Problem = input.parse().unwrap();
 
     // NOTE: Parallellizing this makes it slower.
+    util::count_distinct_cells(
+        problem
+            .antennas
+            .iter()
+            .flat_map(|(_, coords)| {
+                use itertools::Itertools;
+                coords.iter().tuple_combinations::<(_, _)>()
+            })
+            .flat_map(|coord_pair| {
+                problem.pair_antinodes(coord_pair.into(), min_harmonic, max_harmonic)
+            }),
+    )
+}
+
+/// All antinodes within the city, per antenna frequency. I.e. before merging
+/// them, so the same position can show up for multiple frequencies. Without
+/// resonance only the 1st harmonic is taken into account (part A), with it
+/// all of them are (part B).
+pub fn antinodes_by_frequency(
+    input: &str,
+    resonant: bool,
+) -> std::collections::HashMap<char, std::collections::HashSet<util::Coord>> {
+    let problem: Problem = input.parse().unwrap();
+    let (min_harmonic, max_harmonic) = match resonant {
+        false => (1, 1),
+        true => (0, usize::MAX),
+    };
+
     problem
         .antennas
         .iter()
-        .flat_map(|(_, coords)| {
+        .map(|(&frequency, coords)| {
             use itertools::Itertools;
-            coords.iter().tuple_combinations::<(_, _)>()
-        })
-        .map(|coord_pair| -> [&util::Coord; 2] { coord_pair.into() })
-        .flat_map(|coord_pair| {
-            let (&&coord_min, &&coord_max) = itertools::Itertools::minmax(coord_pair.iter())
-                .into_option()
-                .unwrap();
-            let offset = coord_max - coord_min;
-
-            let forward_iter = (0isize..)
-                .map(move |step| coord_max + step * offset)
-                .take_while(|coord| {
-                    !coord.has_negatives() && coord.bounded_by(&problem.city_bounds)
-                });
-
-            let backward_iter = (0isize..)
-                .map(move |step| coord_min - step * offset)
-                .take_while(|coord| {
-                    !coord.has_negatives() && coord.bounded_by(&problem.city_bounds)
-                });
-
-            forward_iter.chain(backward_iter)
+            let antinodes = coords
+                .iter()
+                .tuple_combinations::<(_, _)>()
+                .flat_map(|coord_pair| {
+                    problem.pair_antinodes(coord_pair.into(), min_harmonic, max_harmonic)
+                })
+                .collect();
+            (frequency as char, antinodes)
         })
-        .collect::<std::collections::HashSet<util::Coord>>()
-        .len()
+        .collect()
+}
+
+pub fn part_b(input: &str) -> usize {
+    resonant_antinodes(input, 0, usize::MAX)
 }
 
 #[cfg(test)]
@@ -109,4 +142,43 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn example_harmonics() {
+        util::run_test(|| {
+            let input = util::read_resource("example_08.txt").unwrap();
+            assert_eq!(crate::day_08::resonant_antinodes(&input, 1, 1), 14);
+            assert_eq!(crate::day_08::resonant_antinodes(&input, 0, usize::MAX), 34);
+
+            // Only the antennas themselves, i.e. the four '0' and three 'A' ones.
+            assert_eq!(crate::day_08::resonant_antinodes(&input, 0, 0), 7);
+        });
+    }
+
+    #[test]
+    fn antinodes_by_frequency() {
+        util::run_test(|| {
+            // Two 'a' antennas on a diagonal and two 'B' antennas in a column,
+            // whose antinodes coincide at (3, 4).
+            let input = "......\n..a...\n...a..\n......\n....B.\n....B.\n";
+            let coords = |coords: &[(usize, usize)]| -> std::collections::HashSet<util::Coord> {
+                coords.iter().map(|&e| e.into()).collect()
+            };
+
+            let antinodes = crate::day_08::antinodes_by_frequency(input, false);
+            assert_eq!(antinodes.len(), 2);
+            assert_eq!(antinodes[&'a'], coords(&[(0, 1), (3, 4)]));
+            assert_eq!(antinodes[&'B'], coords(&[(3, 4)]));
+
+            let antinodes = crate::day_08::antinodes_by_frequency(input, true);
+            assert_eq!(
+                antinodes[&'a'],
+                coords(&[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5)])
+            );
+            assert_eq!(
+                antinodes[&'B'],
+                coords(&[(0, 4), (1, 4), (2, 4), (3, 4), (4, 4), (5, 4)])
+            );
+        });
+    }
 }
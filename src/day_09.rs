@@ -8,10 +8,10 @@ struct DenseDiskPointer {
     remaining_length: u8,
 }
 
-/// Calculate (offset + 0) * id + (offset + 1) * id + ... Writing this
-/// out and simplifying ends up with the following formula.
+/// Calculate (offset + 0) * id + (offset + 1) * id + ... I.e. the sum of the
+/// block's positions, multiplied by its ID.
 fn block_checksum(offset: usize, length: usize, id: usize) -> usize {
-    id * (length * offset + (length * (length - 1)) / 2)
+    id * util::arithmetic_block_sum(offset, length)
 }
 
 pub fn part_a(input: &str) -> usize {
@@ -271,6 +271,85 @@ pub fn part_b(input: &str) -> usize {
         .sum::<usize>()
 }
 
+/// Expand the dense disk map into one entry per block, holding the ID of the
+/// file occupying it, or None for free space.
+fn expand_disk(input: &str) -> Vec<Option<usize>> {
+    input
+        .as_bytes()
+        .iter()
+        .map(|length| (length - b'0') as usize)
+        .enumerate()
+        .flat_map(|(block_idx, length)| {
+            let file_id = (block_idx % 2 == 0).then_some(block_idx / 2);
+            std::iter::repeat_n(file_id, length)
+        })
+        .collect()
+}
+
+/// Render the disk after compaction, as in the puzzle's description. I.e. one
+/// character per block, '.' for free space and the file's ID otherwise. IDs
+/// from 10 onwards use letters (a-z, then A-Z), anything larger is shown as
+/// '#'. Compaction moves single blocks, or entire files if whole_file is set.
+/// NOTE: This is meant for debugging, it's a lot slower than part_a() and
+/// part_b().
+pub fn layout_string(input: &str, whole_file: bool) -> String {
+    let mut disk = expand_disk(input);
+
+    match whole_file {
+        false => {
+            // Move the last file block into the first free one, until there's
+            // no more gaps.
+            let mut free_idx = 0;
+            let mut file_idx = disk.len();
+            loop {
+                while free_idx < disk.len() && disk[free_idx].is_some() {
+                    free_idx += 1;
+                }
+                while file_idx > 0 && disk[file_idx - 1].is_none() {
+                    file_idx -= 1;
+                }
+                if free_idx + 1 >= file_idx {
+                    break;
+                }
+                disk.swap(free_idx, file_idx - 1);
+            }
+        }
+        true => {
+            // Try to move each file once, in order of decreasing ID, into the
+            // left-most free span which is large enough.
+            let max_id = disk.iter().flatten().copied().max().unwrap_or(0);
+            for id in (0..=max_id).rev() {
+                let file_start = disk.iter().position(|e| *e == Some(id)).unwrap();
+                let file_length = disk[file_start..]
+                    .iter()
+                    .take_while(|e| **e == Some(id))
+                    .count();
+
+                let free_start = (0..file_start).find(|&start| {
+                    start + file_length <= file_start
+                        && disk[start..start + file_length].iter().all(|e| e.is_none())
+                });
+
+                if let Some(free_start) = free_start {
+                    for offset in 0..file_length {
+                        disk.swap(free_start + offset, file_start + offset);
+                    }
+                }
+            }
+        }
+    }
+
+    disk.iter()
+        .map(|block| match *block {
+            None => '.',
+            Some(id @ 0..10) => (b'0' + id as u8) as char,
+            Some(id @ 10..36) => (b'a' + (id - 10) as u8) as char,
+            Some(id @ 36..62) => (b'A' + (id - 36) as u8) as char,
+            Some(_) => '#',
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -294,4 +373,29 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn example_layout() {
+        util::run_test(|| {
+            let input = util::read_resource("example_09.txt").unwrap();
+            assert_eq!(
+                crate::day_09::layout_string(&input, false),
+                "0099811188827773336446555566.............."
+            );
+            assert_eq!(
+                crate::day_09::layout_string(&input, true),
+                "00992111777.44.333....5555.6666.....8888.."
+            );
+        });
+    }
+
+    #[test]
+    fn layout_large_ids() {
+        util::run_test(|| {
+            // 63 files of a single block each, without any free space.
+            let input = "10".repeat(62) + "1";
+            let expected: String = ('0'..='9').chain('a'..='z').chain('A'..='Z').collect();
+            assert_eq!(crate::day_09::layout_string(&input, false), expected + "#");
+        });
+    }
 }
@@ -51,8 +51,8 @@ impl TopographicMap {
             } else {
                 // Otherwise keep searching by visiting all children that have a
                 // height one larger than current position.
-                for &offset_dir in Self::SEARCH_DIRS.iter() {
-                    let coord: util::Coord = pos + offset_dir;
+                for offset_dir in Self::SEARCH_DIRS.iter() {
+                    let coord = pos + offset_dir;
 
                     if coord.has_negatives() {
                         continue; // Don't bother with invalid coordinates.
@@ -100,7 +100,9 @@ impl std::str::FromStr for TopographicMap {
     }
 }
 
-pub fn part_a(input: &str) -> usize {
+/// Sum of all trailhead scores, i.e. for each trailhead the number of distinct
+/// height 9 positions reachable from it.
+pub fn trailhead_score_sum(input: &str) -> usize {
     let map: TopographicMap = input.parse().unwrap();
     // Count the number of distinct trail ends reached by not allowing
     // revisiting the same cells. That will prevent following multiple trails
@@ -108,13 +110,24 @@ pub fn part_a(input: &str) -> usize {
     map._sum_trails(false)
 }
 
-pub fn part_b(input: &str) -> usize {
+/// Sum of all trailhead ratings, i.e. for each trailhead the number of distinct
+/// trails starting from it. Different trails can share parts, as well as end at
+/// the same position.
+pub fn trailhead_rating_sum(input: &str) -> usize {
     let map: TopographicMap = input.parse().unwrap();
     // Allow revisiting cells, suchs that multiple trails can reuse the same
     // trail subsections.
     map._sum_trails(true)
 }
 
+pub fn part_a(input: &str) -> usize {
+    trailhead_score_sum(input)
+}
+
+pub fn part_b(input: &str) -> usize {
+    trailhead_rating_sum(input)
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -138,4 +151,24 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn example_score_and_rating() {
+        util::run_test(|| {
+            let input = util::read_resource("example_10.txt").unwrap();
+            assert_eq!(crate::day_10::trailhead_score_sum(&input), 36);
+            assert_eq!(crate::day_10::trailhead_rating_sum(&input), 81);
+        });
+    }
+
+    #[test]
+    fn shared_sub_trails() {
+        util::run_test(|| {
+            // A single trailhead and a single trail end, but every path going
+            // only down or right is a separate trail. I.e. 9 choose 4 of them.
+            let input = "012345\n123456\n234567\n345678\n456789\n";
+            assert_eq!(crate::day_10::trailhead_score_sum(input), 1);
+            assert_eq!(crate::day_10::trailhead_rating_sum(input), 126);
+        });
+    }
 }
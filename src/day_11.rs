@@ -436,19 +436,26 @@ pub enum Looping {
 pub const NUM_BLINKS_A: u8 = 25;
 pub const NUM_BLINKS_B: u8 = 75;
 
+/// Upper limit on the number of stones returned by evolve().
+pub const MAX_EVOLVED_STONES: usize = 1 << 22;
+
+fn parse_stones(input: &str) -> Vec<Stone> {
+    input
+        .lines()
+        .next()
+        .unwrap()
+        .split_whitespace()
+        .map(|e| e.parse().unwrap())
+        .collect()
+}
+
 pub fn parse_and_solve(
     input: &str,
     num_blinks: u8,
     execution: Execution,
     looping: Looping,
 ) -> usize {
-    let stones: Vec<Stone> = input
-        .lines()
-        .next()
-        .unwrap()
-        .split_whitespace()
-        .map(|e| e.parse().unwrap())
-        .collect();
+    let stones = parse_stones(input);
 
     // NOTE: Solver is generic, so it's actual type differs depending on
     // whether we parallelize or not.
@@ -464,6 +471,68 @@ pub fn parse_and_solve(
     }
 }
 
+/// Count the stones after the given number of blinks, always using the
+/// iterative solver. Its stack lives on the heap, so unlike the recursive
+/// solver (used for part A & B), it can't overflow the call stack. Prefer this
+/// for large numbers of blinks, or when running on threads with a small stack.
+pub fn count_stones_safe(input: &str, num_blinks: u8) -> usize {
+    parse_and_solve(input, num_blinks, Execution::Sequential, Looping::Iterative)
+}
+
+/// Number of stones after the given number of blinks, or None if that doesn't
+/// fit in a usize. Only tracks how often each distinct stone occurs, since the
+/// order of the stones doesn't matter for their number.
+fn count_stones_checked(input: &str, num_blinks: u8) -> Option<usize> {
+    let mut counts: HashMap<Stone, usize> = HashMap::default();
+    for stone in parse_stones(input) {
+        *counts.entry(stone).or_default() += 1;
+    }
+
+    for _ in 0..num_blinks {
+        let mut next_counts: HashMap<Stone, usize> = HashMap::default();
+        for (stone, count) in counts {
+            let evolved = match StoneEvolution::blink(stone) {
+                StoneEvolution::Single(x) => [Some(x), None],
+                StoneEvolution::Split((x, y)) => [Some(x), Some(y)],
+            };
+            for x in evolved.into_iter().flatten() {
+                let next_count = next_counts.entry(x).or_default();
+                *next_count = next_count.checked_add(count)?;
+            }
+        }
+        counts = next_counts;
+    }
+
+    counts
+        .values()
+        .try_fold(0_usize, |acc, &count| acc.checked_add(count))
+}
+
+/// Returns the stones after the given number of blinks, in order. Since their
+/// number grows exponentially, this fails if there would be more than
+/// MAX_EVOLVED_STONES of them, returning how many there would be instead (or
+/// usize::MAX if that doesn't even fit in a usize).
+pub fn evolve(input: &str, num_blinks: u8) -> Result<Vec<u64>, usize> {
+    match count_stones_checked(input, num_blinks) {
+        Some(num_stones) if num_stones <= MAX_EVOLVED_STONES => {}
+        num_stones => return Err(num_stones.unwrap_or(usize::MAX)),
+    }
+
+    let mut stones = parse_stones(input);
+    for _ in 0..num_blinks {
+        let mut next_stones = Vec::with_capacity(2 * stones.len());
+        for stone in stones {
+            match StoneEvolution::blink(stone) {
+                StoneEvolution::Single(stone) => next_stones.push(stone),
+                StoneEvolution::Split((lhs, rhs)) => next_stones.extend([lhs, rhs]),
+            }
+        }
+        stones = next_stones;
+    }
+
+    Ok(stones)
+}
+
 pub fn part_a(input: &str) -> usize {
     parse_and_solve(
         input,
@@ -495,5 +564,57 @@ mod tests {
         });
     }
 
+    #[test]
+    fn many_blinks() {
+        util::run_test(|| {
+            use crate::day_11::{Execution, Looping};
+
+            // Much more blinks than this overflow the number of stones.
+            let input = "0\n";
+            let expected: usize = 792513901943620967;
+            assert_eq!(crate::day_11::count_stones_safe(input, 100), expected);
+            assert_eq!(
+                crate::day_11::parse_and_solve(
+                    input,
+                    100,
+                    Execution::Sequential,
+                    Looping::Recursive
+                ),
+                expected
+            );
+
+            assert_eq!(
+                crate::day_11::count_stones_safe("125 17\n", crate::day_11::NUM_BLINKS_A),
+                55312
+            );
+        });
+    }
+
+    #[test]
+    fn example_evolve() {
+        util::run_test(|| {
+            let stones = crate::day_11::evolve("125 17\n", 6).unwrap();
+            assert_eq!(stones.len(), 22);
+            assert_eq!(
+                stones,
+                [
+                    2097446912, 14168, 4048, 2, 0, 2, 4, 40, 48, 2024, 40, 48, 80, 96, 2, 8, 6, 7,
+                    6, 0, 3, 2
+                ]
+            );
+            assert_eq!(crate::day_11::evolve("125 17\n", 0).unwrap(), [125, 17]);
+
+            assert_eq!(
+                crate::day_11::evolve("125 17\n", crate::day_11::NUM_BLINKS_A).map(|e| e.len()),
+                Ok(55312)
+            );
+            assert_eq!(crate::day_11::evolve("0\n", 100), Err(792513901943620967));
+
+            // The number of stones doesn't even fit in a usize anymore.
+            assert_eq!(crate::day_11::evolve("0\n", 200), Err(usize::MAX));
+            assert_eq!(crate::day_11::evolve("0\n", u8::MAX), Err(usize::MAX));
+        });
+    }
+
     // There is no example B for this day.
 }
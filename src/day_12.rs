@@ -5,21 +5,40 @@ extern crate nalgebra as na;
 struct MatrixDfsSearcher {
     marked: na::DMatrix<bool>,
     to_visit: VecDeque<util::Coord>,
+    search_dirs: &'static [util::Direction],
 }
 
 impl MatrixDfsSearcher {
-    // TODO: Make this configurable.
-    const SEARCH_DIRS: [util::Direction; 4] = [
+    /// Directions in which a plot has a side, i.e. in which the neighbor
+    /// callbacks get called.
+    const SIDE_DIRS: [util::Direction; 4] = [
         util::Direction::North,
         util::Direction::East,
         util::Direction::South,
         util::Direction::West,
     ];
 
-    fn new(nrows: usize, ncols: usize) -> MatrixDfsSearcher {
+    /// Directions a region can extend in when diagonally adjacent plots are
+    /// considered connected. The side directions must come first.
+    const ALL_DIRS: [util::Direction; 8] = [
+        util::Direction::North,
+        util::Direction::East,
+        util::Direction::South,
+        util::Direction::West,
+        util::Direction::NorthEast,
+        util::Direction::NorthWest,
+        util::Direction::SouthEast,
+        util::Direction::SouthWest,
+    ];
+
+    fn new(nrows: usize, ncols: usize, diagonal: bool) -> MatrixDfsSearcher {
         MatrixDfsSearcher {
             marked: na::DMatrix::from_element(nrows, ncols, false),
             to_visit: VecDeque::default(),
+            search_dirs: match diagonal {
+                false => &Self::SIDE_DIRS,
+                true => &Self::ALL_DIRS,
+            },
         }
     }
 
@@ -50,21 +69,22 @@ impl MatrixDfsSearcher {
             // Notify caller of first visit to this coordinate.
             (first_visit_fn)(visit_pos);
 
-            // Try to visit all neighbors.
-            for &offset_dir in Self::SEARCH_DIRS.iter() {
+            // Try to visit all neighbors. Plots don't have sides in diagonal
+            // directions, so those don't notify the caller.
+            for (dir_idx, &offset_dir) in self.search_dirs.iter().enumerate() {
                 let neighbor_pos: util::Coord = visit_pos + offset_dir;
+                let is_side = dir_idx < Self::SIDE_DIRS.len();
 
-                if neighbor_pos.has_negatives() {
-                    (no_neighbor_fn)(visit_pos, offset_dir);
-                } else {
-                    match self.marked.get_mut(neighbor_pos) {
-                        None => (no_neighbor_fn)(visit_pos, offset_dir),
-                        Some(marked) => {
+                match util::get_mut(&mut self.marked, neighbor_pos) {
+                    None if is_side => (no_neighbor_fn)(visit_pos, offset_dir),
+                    None => (),
+                    Some(marked) => {
+                        if is_side {
                             (neighbor_fn)(visit_pos, neighbor_pos, offset_dir);
-                            if !*marked && (is_neighbor_ok_fn)(visit_pos, neighbor_pos) {
-                                *marked = true;
-                                self.to_visit.push_back(neighbor_pos);
-                            }
+                        }
+                        if !*marked && (is_neighbor_ok_fn)(visit_pos, neighbor_pos) {
+                            *marked = true;
+                            self.to_visit.push_back(neighbor_pos);
                         }
                     }
                 }
@@ -121,7 +141,7 @@ fn parse_input(input: &str) -> na::DMatrix<u8> {
 struct PlotProperties {
     area: usize,
     perimeter: usize,
-    perimeter_coords: [Vec<util::Coord>; MatrixDfsSearcher::SEARCH_DIRS.len()],
+    perimeter_coords: [Vec<util::Coord>; MatrixDfsSearcher::SIDE_DIRS.len()],
 }
 
 impl PlotProperties {
@@ -129,7 +149,7 @@ impl PlotProperties {
         PlotProperties {
             area: 0,
             perimeter: 0,
-            perimeter_coords: [(); MatrixDfsSearcher::SEARCH_DIRS.len()]
+            perimeter_coords: [(); MatrixDfsSearcher::SIDE_DIRS.len()]
                 .map(|_| Vec::<util::Coord>::default()),
         }
     }
@@ -141,13 +161,13 @@ impl PlotProperties {
     }
 }
 
-pub fn part_a(input: &str) -> usize {
+fn perimeter_price(input: &str, diagonal: bool) -> usize {
     let plots = parse_input(input);
     let mut result = 0;
 
     // Reuse storage for a minor speed-up.
     let properties = RefCell::new(PlotProperties::new());
-    let mut searcher = MatrixDfsSearcher::new(plots.nrows(), plots.ncols());
+    let mut searcher = MatrixDfsSearcher::new(plots.nrows(), plots.ncols(), diagonal);
 
     // Go over each plot and gather neighboring plots of the same type.
     for (plot_idx, plot_type) in plots.iter().enumerate() {
@@ -253,13 +273,20 @@ fn count_num_edges(coords: &mut [util::Coord], edge_position: util::Direction) -
         .count()
 }
 
-pub fn part_b(input: &str) -> usize {
+/// Returns for every region its letter, a representative plot (the first one
+/// in column-major order), its area and its number of sides. The regions are
+/// in the same order as their representatives.
+pub fn region_sides(input: &str) -> Vec<(char, util::Coord, usize, usize)> {
+    _region_sides(input, false)
+}
+
+fn _region_sides(input: &str, diagonal: bool) -> Vec<(char, util::Coord, usize, usize)> {
     let plots = parse_input(input);
-    let mut result = 0;
+    let mut result = Vec::new();
 
     // Reuse storage for a minor speed-up.
     let properties = RefCell::new(PlotProperties::new());
-    let mut searcher = MatrixDfsSearcher::new(plots.nrows(), plots.ncols());
+    let mut searcher = MatrixDfsSearcher::new(plots.nrows(), plots.ncols(), diagonal);
 
     // Go over each plot and gather neighboring plots of the same type.
     for (plot_idx, plot_type) in plots.iter().enumerate() {
@@ -290,16 +317,20 @@ pub fn part_b(input: &str) -> usize {
             .sum();
 
         if num_edges > 0 {
-            let cost = properties.borrow().area * num_edges;
             log::debug!(
                 "Region {} (start @ {:?}): {} * {} => {}",
                 *plot_type as char,
                 start_pos,
                 properties.borrow().area,
                 num_edges,
-                cost
+                properties.borrow().area * num_edges
             );
-            result += cost;
+            result.push((
+                *plot_type as char,
+                start_pos,
+                properties.borrow().area,
+                num_edges,
+            ));
 
             // Reset properties for next iteration.
             properties.borrow_mut().reset();
@@ -309,6 +340,28 @@ pub fn part_b(input: &str) -> usize {
     result
 }
 
+/// Calculate the total fencing price. If diagonal is set, plots of the same
+/// type which only touch diagonally are part of the same region. If
+/// count_sides is set, a region's price is its area times its number of sides
+/// (part B), otherwise its area times its perimeter (part A).
+pub fn solve(input: &str, diagonal: bool, count_sides: bool) -> usize {
+    match count_sides {
+        false => perimeter_price(input, diagonal),
+        true => _region_sides(input, diagonal)
+            .into_iter()
+            .map(|(_, _, area, num_sides)| area * num_sides)
+            .sum(),
+    }
+}
+
+pub fn part_a(input: &str) -> usize {
+    solve(input, false, false)
+}
+
+pub fn part_b(input: &str) -> usize {
+    solve(input, false, true)
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -355,6 +408,52 @@ mod tests {
         });
     }
 
+    #[test]
+    fn example_b_1_region_sides() {
+        util::run_test(|| {
+            let regions = crate::day_12::region_sides("AAAA\nBBCD\nBBCC\nEEEC\n");
+            let expected: Vec<(char, util::Coord, usize, usize)> = vec![
+                ('A', (0, 0).into(), 4, 4),
+                ('B', (1, 0).into(), 4, 4),
+                ('E', (3, 0).into(), 3, 4),
+                ('C', (1, 2).into(), 4, 8),
+                ('D', (1, 3).into(), 1, 4),
+            ];
+            assert_eq!(regions, expected);
+
+            let price: usize = regions.iter().map(|(_, _, area, sides)| area * sides).sum();
+            assert_eq!(price, 80);
+        });
+    }
+
+    #[test]
+    fn diagonal_connectivity() {
+        util::run_test(|| {
+            // All A's touch diagonally, as do both B's.
+            let input = "AAB\nABA\nBAA\n";
+            assert_eq!(crate::day_12::solve(input, false, false), 60);
+            assert_eq!(crate::day_12::solve(input, false, true), 48);
+            assert_eq!(crate::day_12::solve(input, true, false), 132);
+            assert_eq!(crate::day_12::solve(input, true, true), 108);
+
+            // The two B regions inside the A region touch diagonally.
+            let input = "AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA\n";
+            assert_eq!(crate::day_12::solve(input, false, false), 1184);
+            assert_eq!(crate::day_12::solve(input, false, true), 368);
+            assert_eq!(crate::day_12::solve(input, true, false), 1248);
+            assert_eq!(crate::day_12::solve(input, true, true), 400);
+
+            // Without diagonal neighbors, nothing changes.
+            let input = "AAAA\nBBCD\nBBCC\nEEEC\n";
+            for count_sides in [false, true] {
+                assert_eq!(
+                    crate::day_12::solve(input, true, count_sides),
+                    crate::day_12::solve(input, false, count_sides)
+                );
+            }
+        });
+    }
+
     #[test]
     fn example_b_2() {
         util::run_test(|| {
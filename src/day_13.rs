@@ -57,16 +57,16 @@ impl std::str::FromStr for ClawMachine {
 }
 
 pub fn part_a(input: &str) -> usize {
-    input
-        .split("\n\n")
+    util::split_sections_n(input)
+        .into_iter()
         .map(|sub| sub.parse::<ClawMachine>().unwrap())
         .filter_map(|e| e.num_tokens_to_win())
         .sum()
 }
 
 pub fn part_b(input: &str) -> usize {
-    input
-        .split("\n\n")
+    util::split_sections_n(input)
+        .into_iter()
         .map(|sub| sub.parse::<ClawMachine>().unwrap())
         .map(|mut machine| {
             const OFFSET: isize = 10000000000000;
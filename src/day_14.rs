@@ -1,5 +1,7 @@
 use nalgebra as na;
+#[cfg(feature = "simd")]
 use std::simd::num::*;
+#[cfg(feature = "simd")]
 use std::simd::*;
 
 #[derive(Debug)]
@@ -16,10 +18,10 @@ impl std::str::FromStr for Robot {
             let start_x = s.find('=').unwrap() + 1;
             let end_x = start_x + s[start_x..].find(',').unwrap();
             let start_y = end_x + 1;
-            util::Coord {
-                row: s[start_y..].parse().unwrap(),
-                col: s[start_x..end_x].parse().unwrap(),
-            }
+            util::Coord::from_signed(
+                s[start_y..].parse().unwrap(),
+                s[start_x..end_x].parse().unwrap(),
+            )
         };
 
         let mut coords = s.split_whitespace().map(|e| parse_coord(e));
@@ -63,10 +65,13 @@ impl Robot {
 }
 
 fn coord_to_quadrant(pos: util::Coord, room_size: util::Coord) -> Option<usize> {
-    let calc_side = |e, room| match ((room - 1) as f64) / (e as f64) {
-        value if value < 2. => Some(0),
-        value if value > 2. => Some(1),
-        _ => None,
+    // Compare against the midline at (room - 1) / 2, but multiply both sides
+    // by 2 to stay in integers. That way there's no midline for even room
+    // sizes, while an odd room size has one exactly on a cell.
+    let calc_side = |e: isize, room: isize| match (2 * e).cmp(&(room - 1)) {
+        std::cmp::Ordering::Greater => Some(0),
+        std::cmp::Ordering::Less => Some(1),
+        std::cmp::Ordering::Equal => None,
     };
 
     let side_col = calc_side(pos.col, room_size.col);
@@ -105,17 +110,19 @@ pub fn part_a(input: &str) -> usize {
     part_a_configurable(input, ROOM_SIZE)
 }
 
+#[cfg(feature = "simd")]
 const fn variance_swizzle_indices<const SIMD_LANES: usize, const OFFSET: usize>(
 ) -> [usize; SIMD_LANES] {
     let mut result = [0; SIMD_LANES];
     let mut i = 0; // Can't use a for-loop as that depends on traits, which can't be used in const fn.
     while i < SIMD_LANES {
-        result[i] = i;
+        result[i] = i + OFFSET;
         i += 1;
     }
     result
 }
 
+#[cfg(feature = "simd")]
 fn calculate_dispersion_coefficient<const MODULO: u8>(
     positions: &[u8],
     velocities: &[u8],
@@ -176,11 +183,14 @@ fn calculate_dispersion_coefficient<const MODULO: u8>(
     let remaining_pos = &positions[num_chunks * SIMD_LANES..];
     let remaining_vel = &velocities[num_chunks * SIMD_LANES..];
 
-    for (&pos, &vel) in remaining_pos.iter().zip(remaining_vel.iter()) {
-        let loc = (pos as u16 + step as u16 * vel as u16) % MODULO as u16;
-        simd_mean.as_mut_array()[0] += loc;
-        simd_variance_lo.as_mut_array()[0] += loc as u32 * loc as u32;
-    }
+    let (remaining_sum, remaining_sum_squared) = util::sum_and_sum_squared(
+        remaining_pos
+            .iter()
+            .zip(remaining_vel.iter())
+            .map(|(&pos, &vel)| (pos as u16 + step as u16 * vel as u16) % MODULO as u16),
+    );
+    simd_mean.as_mut_array()[0] += remaining_sum as u16;
+    simd_variance_lo.as_mut_array()[0] += remaining_sum_squared;
 
     let num_samples = positions.len() as u16;
     let mean = simd_mean.reduce_sum() / num_samples;
@@ -192,6 +202,31 @@ fn calculate_dispersion_coefficient<const MODULO: u8>(
     variance as f32 / mean as f32
 }
 
+/// Same as the SIMD version, but processes one robot at a time.
+#[cfg(not(feature = "simd"))]
+fn calculate_dispersion_coefficient<const MODULO: u8>(
+    positions: &[u8],
+    velocities: &[u8],
+    step: u8,
+) -> f32 {
+    assert!(step < MODULO);
+    assert_eq!(positions.len(), velocities.len());
+
+    let (sum, sum_squared) = util::sum_and_sum_squared(
+        positions
+            .iter()
+            .zip(velocities.iter())
+            .map(|(&pos, &vel)| (pos as u32 + step as u32 * vel as u32) % MODULO as u32),
+    );
+
+    let num_samples = positions.len() as u32;
+    let mean = sum / num_samples;
+    let variance = (sum_squared / num_samples) - mean.pow(2);
+
+    // Calculate statistical dispersion as variance divided by mean.
+    variance as f32 / mean as f32
+}
+
 fn find_step_with_min_dispersion<const MODULO: u8>(positions: &[u8], velocities: &[u8]) -> u8 {
     // NOTE: Parallelization this makes things much slower.
     let num_steps = (0..MODULO)
@@ -238,29 +273,49 @@ pub fn part_b(input: &str) -> usize {
 
     // Now we know that given a solution of N steps, N modulo respectively the
     // room's number of rows or columns must equal one of the two values found.
-    // To solve, use the Chinese remainder theorem:
-    //   N == (row_steps_remainder + N * ROOM_SIZE.row)
-    //      iif N % ROOM_SIZE.col == col_steps_remainder.
-    // NOTE: The runtime of this loop is utterly negligible compared to the rest
-    // of the code, no point in optimizing it.
-    let num_steps = (0..ROOM_SIZE.col as u8)
-        .map(|step| (row_steps_remainder as u16 + step as u16 * ROOM_SIZE.row as u16) as u16)
-        .find(|e| *e % ROOM_SIZE.col as u16 == col_steps_remainder as u16)
-        .unwrap();
-
-    log::debug!("num steps => {}{}", num_steps, {
+    // To solve, use the Chinese remainder theorem.
+    let (num_steps, _) = util::crt(&[
+        (row_steps_remainder as i64, ROOM_SIZE.row as i64),
+        (col_steps_remainder as i64, ROOM_SIZE.col as i64),
+    ])
+    .unwrap();
+
+    log::debug!("num steps => {}\n{}", num_steps, {
         let mut map =
-            na::DMatrix::from_element(ROOM_SIZE.row as usize, ROOM_SIZE.col as usize, '.');
+            na::DMatrix::from_element(ROOM_SIZE.row as usize, ROOM_SIZE.col as usize, false);
         let robots: Vec<Robot> = input.lines().map(|e| e.parse().unwrap()).collect();
         for robot in robots.iter() {
-            map[robot.step(&ROOM_SIZE, num_steps as isize)] = '#'
+            map[robot.step(&ROOM_SIZE, num_steps as isize)] = true
         }
-        map
+        util::render_grid(&map, |&e| if e { '#' } else { '.' })
     });
 
     num_steps as usize
 }
 
+/// Same as part_b(), but without relying on the rows and columns of the robots
+/// repeating independently. Instead, simulate every step until the robots are
+/// back at their starting positions, and return the one at which they are
+/// clustered most closely, i.e. with the lowest sum of the variances of their
+/// rows and columns. A lot slower, but useful as a reference.
+pub fn part_b_brute_force(input: &str) -> usize {
+    let robots: Vec<Robot> = input.lines().map(|e| e.parse().unwrap()).collect();
+
+    // NOTE: Both room dimensions are prime, so their LCM is their product.
+    let period = ROOM_SIZE.row * ROOM_SIZE.col;
+
+    (0..period)
+        .map(|step| {
+            let positions: Vec<_> = robots.iter().map(|e| e.step(&ROOM_SIZE, step)).collect();
+            let spread = util::variance(positions.iter().map(|e| e.row as f64)).unwrap()
+                + util::variance(positions.iter().map(|e| e.col as f64)).unwrap();
+            (step, spread)
+        })
+        .min_by(|lhs, rhs| lhs.1.total_cmp(&rhs.1))
+        .unwrap()
+        .0 as usize
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -278,5 +333,168 @@ mod tests {
         });
     }
 
+    #[test]
+    fn quadrant_odd_room() {
+        use crate::day_14::coord_to_quadrant;
+        use util::Coord;
+
+        let room_size = Coord { row: 7, col: 11 };
+        assert_eq!(
+            coord_to_quadrant(Coord { row: 0, col: 0 }, room_size),
+            Some(3)
+        );
+        assert_eq!(
+            coord_to_quadrant(Coord { row: 0, col: 10 }, room_size),
+            Some(2)
+        );
+        assert_eq!(
+            coord_to_quadrant(Coord { row: 6, col: 0 }, room_size),
+            Some(1)
+        );
+        assert_eq!(
+            coord_to_quadrant(Coord { row: 6, col: 10 }, room_size),
+            Some(0)
+        );
+        assert_eq!(
+            coord_to_quadrant(Coord { row: 2, col: 4 }, room_size),
+            Some(3)
+        );
+        assert_eq!(
+            coord_to_quadrant(Coord { row: 4, col: 6 }, room_size),
+            Some(0)
+        );
+
+        // Anything on either centre line doesn't belong to a quadrant.
+        assert_eq!(coord_to_quadrant(Coord { row: 3, col: 0 }, room_size), None);
+        assert_eq!(coord_to_quadrant(Coord { row: 0, col: 5 }, room_size), None);
+        assert_eq!(coord_to_quadrant(Coord { row: 3, col: 5 }, room_size), None);
+    }
+
+    #[test]
+    fn quadrant_even_room() {
+        use crate::day_14::coord_to_quadrant;
+        use util::Coord;
+
+        // No centre lines, so every cell belongs to a quadrant.
+        let room_size = Coord { row: 4, col: 6 };
+        assert_eq!(
+            coord_to_quadrant(Coord { row: 1, col: 2 }, room_size),
+            Some(3)
+        );
+        assert_eq!(
+            coord_to_quadrant(Coord { row: 1, col: 3 }, room_size),
+            Some(2)
+        );
+        assert_eq!(
+            coord_to_quadrant(Coord { row: 2, col: 2 }, room_size),
+            Some(1)
+        );
+        assert_eq!(
+            coord_to_quadrant(Coord { row: 2, col: 3 }, room_size),
+            Some(0)
+        );
+
+        let room_size = Coord { row: 2, col: 2 };
+        assert_eq!(
+            coord_to_quadrant(Coord { row: 0, col: 0 }, room_size),
+            Some(3)
+        );
+        assert_eq!(
+            coord_to_quadrant(Coord { row: 1, col: 1 }, room_size),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn dispersion_coefficient() {
+        use crate::day_14::calculate_dispersion_coefficient;
+
+        // 20 robots, so with SIMD both the chunks and the remainder get exercised.
+        let positions: Vec<u8> = (0..20).collect();
+        let velocities = vec![1_u8; positions.len()];
+
+        // Locations 0..20: mean 9, variance 123 - 81 = 42.
+        assert_eq!(
+            calculate_dispersion_coefficient::<101>(&positions, &velocities, 0),
+            42.0 / 9.0
+        );
+        // Locations 5..25: mean 14, variance 243 - 196 = 47.
+        assert_eq!(
+            calculate_dispersion_coefficient::<101>(&positions, &velocities, 5),
+            47.0 / 14.0
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "simd")]
+    fn variance_swizzle_indices() {
+        use crate::day_14::variance_swizzle_indices;
+
+        assert_eq!(variance_swizzle_indices::<4, 0>(), [0, 1, 2, 3]);
+        assert_eq!(variance_swizzle_indices::<4, 4>(), [4, 5, 6, 7]);
+    }
+
+    #[test]
+    #[cfg(feature = "simd")]
+    fn dispersion_coefficient_upper_lanes() {
+        use crate::day_14::calculate_dispersion_coefficient;
+
+        // A single full SIMD chunk where only the upper half of the lanes is
+        // non-zero. The variance must include the squares of those lanes.
+        let positions: Vec<u8> = [[0_u8; 8], [10_u8; 8]].concat();
+        let velocities = vec![0_u8; positions.len()];
+
+        // Mean 5, variance 50 - 25 = 25.
+        assert_eq!(
+            calculate_dispersion_coefficient::<101>(&positions, &velocities, 0),
+            25.0 / 5.0
+        );
+    }
+
+    #[test]
+    fn clustered_robots() {
+        util::run_test(|| {
+            use crate::day_14::ROOM_SIZE;
+
+            // Robots that all end up in a small square in the middle of the
+            // room after a given number of steps, and are spread out otherwise.
+            const NUM_STEPS: isize = 6789;
+            let mut state = 0x2024_u32;
+            let mut random = |modulo: isize| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as isize % modulo
+            };
+
+            let input: String = (0..300)
+                .map(|_| {
+                    let end = util::Coord {
+                        row: 50 + random(4),
+                        col: 50 + random(4),
+                    };
+                    let velocity = util::Coord {
+                        row: random(41) - 20,
+                        col: random(41) - 20,
+                    };
+                    let start = end - NUM_STEPS * velocity;
+                    format!(
+                        "p={},{} v={},{}\n",
+                        start.col.rem_euclid(ROOM_SIZE.col),
+                        start.row.rem_euclid(ROOM_SIZE.row),
+                        velocity.col,
+                        velocity.row
+                    )
+                })
+                .collect();
+
+            assert_eq!(crate::day_14::part_b(&input), NUM_STEPS as usize);
+            assert_eq!(
+                crate::day_14::part_b_brute_force(&input),
+                NUM_STEPS as usize
+            );
+        });
+    }
+
     // No example for part B.
 }
@@ -14,7 +14,10 @@ impl Problem {
     }
 
     fn gps_coord_sum(&self) -> usize {
-        log::debug!("Summing warehouse: {}", self.warehouse);
+        log::debug!(
+            "Summing warehouse:\n{}",
+            util::render_grid(&self.warehouse, |&e| e)
+        );
         self.warehouse
             .iter()
             .enumerate()
@@ -36,21 +39,15 @@ impl Problem {
         let widened_warehouse = na::DMatrix::from_row_iterator(
             self.warehouse.nrows(),
             2 * self.warehouse.ncols(),
-            self.warehouse.row_iter().flatten().flat_map(|e| {
-                match e {
-                    '.' => "..",
-                    '#' => "##",
-                    'O' => "[]",
-                    '@' => "@.",
-                    _ => unreachable!(),
-                }
-                .chars()
-            }),
+            self.warehouse
+                .row_iter()
+                .flatten()
+                .flat_map(|&e| widen_glyph(e).chars()),
         );
         self.warehouse = widened_warehouse;
         self.robot_pos.col *= 2;
 
-        log::debug!("Widened:\n{}", self.warehouse);
+        log::debug!("Widened:\n{}", util::render_grid(&self.warehouse, |&e| e));
     }
 
     fn move_robot<T>(&mut self, gather_to_move: T)
@@ -85,47 +82,86 @@ impl Problem {
                 log::debug!(
                     "Moved robot and {} boxes:\n{}",
                     to_move.len() - 1,
-                    self.warehouse
+                    util::render_grid(&self.warehouse, |&e| e)
                 );
             }
         }
     }
 }
 
-impl std::str::FromStr for Problem {
-    type Err = std::string::ParseError;
+/// Every square of the warehouse becomes two squares wide in part B.
+fn widen_glyph(glyph: char) -> &'static str {
+    match glyph {
+        '.' => "..",
+        '#' => "##",
+        'O' => "[]",
+        '@' => "@.",
+        _ => unreachable!(),
+    }
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let cols = s.lines().next().unwrap().len();
-        let rows = s
-            .lines()
-            .enumerate()
-            .skip_while(|(_, e)| e.len() != 0)
-            .map(|(idx, _)| idx)
-            .next()
-            .unwrap();
+/// Widen the warehouse map in the given text, the same way as is done for part
+/// B. Only the map (i.e. the first section of the input) is widened and
+/// returned, any moves following it are dropped.
+///
+/// Panics if the map contains anything other than '.', '#', 'O' or '@'.
+pub fn widen_map_text(warehouse: &str) -> String {
+    let (warehouse_str, _) = util::split_sections(warehouse);
+    warehouse_str
+        .lines()
+        .flat_map(|line| line.chars().map(widen_glyph).chain(std::iter::once("\n")))
+        .collect()
+}
+
+/// Maps the glyphs used in the list of moves onto the direction the robot
+/// moves in. Glyphs that aren't mapped (e.g. whitespace) are ignored.
+pub struct MoveSet {
+    directions: [Option<util::Direction>; 256],
+}
+
+impl MoveSet {
+    pub fn new<T>(glyphs: T) -> MoveSet
+    where
+        T: IntoIterator<Item = (u8, util::Direction)>,
+    {
+        let mut result = MoveSet {
+            directions: [None; 256],
+        };
+        for (glyph, dir) in glyphs {
+            result.directions[glyph as usize] = Some(dir);
+        }
+        result
+    }
+
+    fn direction(&self, glyph: u8) -> Option<util::Direction> {
+        self.directions[glyph as usize]
+    }
+}
+
+impl Default for MoveSet {
+    /// The standard "^", ">", "v" and "<" arrows.
+    fn default() -> Self {
+        MoveSet {
+            directions: std::array::from_fn(|e| util::Direction::from_ascii(e as u8)),
+        }
+    }
+}
+
+impl Problem {
+    /// Parse the warehouse map and find the robot in it. Returns the problem
+    /// without any moves, and the remainder of the input containing them.
+    fn parse_warehouse(s: &str) -> Result<(Problem, &str), util::InputError> {
+        // The warehouse map and the moves are separated by an empty line.
+        let (warehouse_str, moves_str) = util::split_sections(s);
+        let (rows, cols) = util::grid_dimensions(warehouse_str)?;
 
         let mut result = Problem {
             warehouse: na::DMatrix::from_row_iterator(
                 rows,
                 cols,
-                s.lines()
-                    .take_while(|e| e.len() != 0)
-                    .flat_map(|line| line.chars()),
+                warehouse_str.lines().flat_map(|line| line.chars()),
             ),
-            moves: s
-                .lines()
-                .skip_while(|e| e.len() != 0)
-                .flat_map(|line| {
-                    line.as_bytes().iter().map(|e| match e {
-                        b'^' => util::Direction::North,
-                        b'>' => util::Direction::East,
-                        b'v' => util::Direction::South,
-                        b'<' => util::Direction::West,
-                        _ => unreachable!(),
-                    })
-                })
-                .collect(),
+            moves: Vec::new(),
             robot_pos: util::Coord { row: 0, col: 0 },
         };
 
@@ -133,13 +169,8 @@ impl std::str::FromStr for Problem {
             result
                 .warehouse
                 .iter()
-                .enumerate()
-                .filter_map(|(idx, e)| match *e == '@' {
-                    true => Some(idx),
-                    false => None,
-                })
-                .next()
-                .unwrap(),
+                .position(|e| *e == '@')
+                .ok_or(util::InputError::MissingMarker("robot"))?,
             rows,
             cols,
         );
@@ -147,12 +178,41 @@ impl std::str::FromStr for Problem {
         // Don't track robot position on map.
         //result.warehouse[result.robot_pos] = '.';
 
+        Ok((result, moves_str))
+    }
+
+    /// Parse the input, with the moves written using the given glyphs. Unlike
+    /// the regular parsing, characters which aren't in the move set are simply
+    /// skipped.
+    fn parse_with_move_set(s: &str, move_set: &MoveSet) -> Result<Problem, util::InputError> {
+        let (mut result, moves_str) = Self::parse_warehouse(s)?;
+        result.moves = moves_str
+            .bytes()
+            .filter_map(|e| move_set.direction(e))
+            .collect();
         Ok(result)
     }
 }
 
-pub fn part_a(input: &str) -> usize {
-    let mut problem: Problem = input.parse().unwrap();
+impl std::str::FromStr for Problem {
+    type Err = util::InputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mut result, moves_str) = Self::parse_warehouse(s)?;
+        result.moves = moves_str
+            .lines()
+            .flat_map(|line| {
+                line.as_bytes().iter().map(|&e| {
+                    util::Direction::from_ascii(e)
+                        .ok_or(util::InputError::UnexpectedChar(e as char))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(result)
+    }
+}
+
+fn solve_part_a(problem: &mut Problem) -> usize {
     log::debug!("{:?}", problem);
 
     let gather_to_move = |result: &mut Vec<util::Coord>,
@@ -193,8 +253,7 @@ pub fn part_a(input: &str) -> usize {
     problem.gps_coord_sum()
 }
 
-pub fn part_b(input: &str) -> usize {
-    let mut problem: Problem = input.parse().unwrap();
+fn solve_part_b(problem: &mut Problem) -> usize {
     log::debug!("{:?}", problem);
 
     // Expand problem by making it twice as wide.
@@ -293,6 +352,30 @@ pub fn part_b(input: &str) -> usize {
     problem.gps_coord_sum()
 }
 
+/// Only runs the parser on the input, without solving anything. Returns an
+/// error describing the problem if the input is malformed.
+pub fn parse(input: &str) -> Result<(), util::InputError> {
+    input.parse::<Problem>().map(|_| ())
+}
+
+pub fn part_a(input: &str) -> usize {
+    solve_part_a(&mut input.parse().unwrap())
+}
+
+pub fn part_b(input: &str) -> usize {
+    solve_part_b(&mut input.parse().unwrap())
+}
+
+/// Same as part_a(), but with the moves written using the given glyphs.
+pub fn part_a_with_move_set(input: &str, move_set: &MoveSet) -> usize {
+    solve_part_a(&mut Problem::parse_with_move_set(input, move_set).unwrap())
+}
+
+/// Same as part_b(), but with the moves written using the given glyphs.
+pub fn part_b_with_move_set(input: &str, move_set: &MoveSet) -> usize {
+    solve_part_b(&mut Problem::parse_with_move_set(input, move_set).unwrap())
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -334,4 +417,76 @@ mod tests {
             crate::day_15::part_b(&util::read_resource("example_15-part_3.txt").unwrap());
         });
     }
+
+    #[test]
+    fn move_set_ignores_other_chars() {
+        util::run_test(|| {
+            use crate::day_15::{MoveSet, Problem};
+            use util::Direction;
+
+            const WAREHOUSE: &str = "#######\n#...O.#\n#.@...#\n#.....#\n#######\n\n";
+            let solve = |moves: &str, move_set: &MoveSet| {
+                let input = format!("{}{}", WAREHOUSE, moves);
+                let mut problem = Problem::parse_with_move_set(&input, move_set).unwrap();
+                let gps_coord_sum = crate::day_15::solve_part_a(&mut problem);
+                (problem.moves.len(), problem.robot_pos, gps_coord_sum)
+            };
+
+            // Two steps east, a blocked push north, then another step east.
+            let expected = (4, util::Coord { row: 2, col: 5 }, 104);
+            assert_eq!(solve("> >\n ^ \n>\n", &MoveSet::default()), expected);
+
+            let wasd = MoveSet::new([
+                (b'w', Direction::North),
+                (b'd', Direction::East),
+                (b's', Direction::South),
+                (b'a', Direction::West),
+            ]);
+            assert_eq!(solve("d d\n w \nd\n", &wasd), expected);
+
+            // Regular parsing doesn't allow for any other characters.
+            assert_eq!(
+                format!("{}> >\n", WAREHOUSE)
+                    .parse::<Problem>()
+                    .unwrap_err(),
+                util::InputError::UnexpectedChar(' ')
+            );
+        });
+    }
+
+    #[test]
+    fn widen_map_text() {
+        util::run_test(|| {
+            let input = "#####\n#.O@#\n#####\n\n<>\n";
+            let expected = "##########\n##..[]@.##\n##########\n";
+            assert_eq!(crate::day_15::widen_map_text(input), expected);
+
+            // Same result as widening the parsed map.
+            let mut problem: crate::day_15::Problem = input.parse().unwrap();
+            problem.widen();
+            assert_eq!(util::render_grid(&problem.warehouse, |&e| e), expected);
+        });
+    }
+
+    #[test]
+    fn malformed_input() {
+        util::run_test(|| {
+            assert_eq!(
+                "####\n#..#\n####\n\n<>"
+                    .parse::<crate::day_15::Problem>()
+                    .unwrap_err(),
+                util::InputError::MissingMarker("robot")
+            );
+            assert_eq!(
+                "####\n#@.#\n####\n\n<x>"
+                    .parse::<crate::day_15::Problem>()
+                    .unwrap_err(),
+                util::InputError::UnexpectedChar('x')
+            );
+            assert!(matches!(
+                "####\n#@.\n####\n\n<>".parse::<crate::day_15::Problem>(),
+                Err(util::InputError::RaggedGrid { row: 1, .. })
+            ));
+        });
+    }
 }
@@ -95,7 +95,11 @@ impl DirectionProperties for util::Direction {
 }
 
 impl Problem {
-    fn _find_cheapest_paths(&self) -> [na::DMatrix<usize>; 4] {
+    fn _find_cheapest_paths(
+        &self,
+        start_dir: util::Direction,
+        end_dir: Option<util::Direction>,
+    ) -> [na::DMatrix<usize>; 4] {
         // Just Dijkstra, keeping track from which direction a cell was visited.
 
         // NOTE: This priority queue requires that a key pushed to the heap must
@@ -113,7 +117,7 @@ impl Problem {
             0,
             PathCell {
                 pos: self.start_pos,
-                dir: util::Direction::East,
+                dir: start_dir,
             },
         );
 
@@ -128,7 +132,7 @@ impl Problem {
             }
 
             costs[cur.dir.to_idx()][cur.pos] = cur_cost;
-            if cur.pos == self.end_pos {
+            if (cur.pos == self.end_pos) && end_dir.is_none_or(|dir| dir == cur.dir) {
                 // If target is reached, bail out.
                 log::debug!("Found end: {:?}", cur);
                 break;
@@ -177,7 +181,28 @@ impl Problem {
     }
 
     fn find_cheapest_path(&self) -> usize {
-        itertools::min(self._find_cheapest_paths().map(|e| e[self.end_pos])).unwrap()
+        self.find_cheapest_path_with_directions(util::Direction::East, None)
+            .unwrap()
+    }
+
+    /// Cost of the cheapest path when starting in the given direction, and
+    /// optionally arriving at the end in the given direction. Returns None if
+    /// there's no such path.
+    fn find_cheapest_path_with_directions(
+        &self,
+        start_dir: util::Direction,
+        end_dir: Option<util::Direction>,
+    ) -> Option<usize> {
+        let costs = self._find_cheapest_paths(start_dir, end_dir);
+        let cost = match end_dir {
+            None => itertools::min(costs.map(|e| e[self.end_pos])).unwrap(),
+            Some(dir) => costs[dir.to_idx()][self.end_pos],
+        };
+
+        match cost {
+            usize::MAX => None,
+            cost => Some(cost),
+        }
     }
 
     fn to_idx(&self, pos: &util::Coord) -> usize {
@@ -257,51 +282,115 @@ impl Problem {
             .count_ones() as usize
     }
 
+    /// Number of distinct cheapest paths from start to end, where two paths
+    /// are distinct if they differ in at least one step or turn. Saturates at
+    /// usize::MAX, since the count can grow exponentially with the maze size.
+    fn num_optimal_paths(&self) -> usize {
+        let costs = self._find_cheapest_paths(util::Direction::East, None);
+        let min_cost = itertools::min(costs.iter().map(|e| e[self.end_pos])).unwrap();
+        if min_cost == usize::MAX {
+            return 0;
+        }
+
+        // Every state which is cheaper than the end is final, so handle them in
+        // order of increasing cost. Then all ways of reaching a state's
+        // predecessors are known by the time the state itself is handled.
+        let mut states: Vec<(usize, PathCell)> = costs
+            .iter()
+            .enumerate()
+            .flat_map(|(dir_idx, dir_costs)| {
+                util::iter_col_major(dir_costs)
+                    .filter(|&(_, &cost)| cost <= min_cost)
+                    .map(move |(pos, &cost)| {
+                        let dir = <util::Direction as DirectionProperties>::from_idx(dir_idx);
+                        (cost, PathCell { pos, dir })
+                    })
+            })
+            .collect();
+        states.sort_unstable_by_key(|(cost, _)| *cost);
+
+        let mut num_paths: [na::DMatrix<usize>; 4] =
+            array::from_fn(|_| na::DMatrix::zeros(self.maze.nrows(), self.maze.ncols()));
+
+        for (cost, cur) in states {
+            if cost == 0 {
+                num_paths[cur.dir.to_idx()][cur.pos] = 1;
+                continue;
+            }
+
+            // Same options as in _extract_num_paths_cells(), i.e. a state is
+            // reached either by a step forward or by a turn.
+            let mut count = 0usize;
+            let prev_pos = cur.pos + cur.dir.reverse();
+            if costs[cur.dir.to_idx()].get(prev_pos.as_pair()) == Some(&(cost - 1)) {
+                count = count.saturating_add(num_paths[cur.dir.to_idx()][prev_pos]);
+            }
+            for turn in cur.dir.turns() {
+                if (cost >= 1000) && (costs[turn.to_idx()][cur.pos] == cost - 1000) {
+                    count = count.saturating_add(num_paths[turn.to_idx()][cur.pos]);
+                }
+            }
+            num_paths[cur.dir.to_idx()][cur.pos] = count;
+        }
+
+        num_paths
+            .iter()
+            .zip(costs.iter())
+            .filter(|(_, dir_costs)| dir_costs[self.end_pos] == min_cost)
+            .fold(0usize, |acc, (dir_paths, _)| {
+                acc.saturating_add(dir_paths[self.end_pos])
+            })
+    }
+
     fn find_num_path_cells(&self) -> usize {
-        let costs = self._find_cheapest_paths();
+        let costs = self._find_cheapest_paths(util::Direction::East, None);
         self._extract_num_paths_cells(&costs)
     }
 }
 
 impl std::str::FromStr for Problem {
-    type Err = std::string::ParseError;
+    type Err = util::InputError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let rows = s.lines().count();
-        let cols = s.lines().next().unwrap().len();
-
-        let mut start_idx: usize = 0;
-        let mut end_idx: usize = 0;
-        let mut result = Problem {
-            maze: na::DMatrix::from_row_iterator(
-                rows,
-                cols,
-                s.lines()
-                    .flat_map(|line| line.chars())
-                    .enumerate()
-                    .inspect(|(idx, e)| match e {
-                        'S' => start_idx = *idx,
-                        'E' => end_idx = *idx,
-                        _ => (),
-                    })
-                    .map(|(_, e)| e),
-            ),
-            start_pos: util::Coord { row: 0, col: 0 },
-            end_pos: util::Coord { row: 0, col: 0 },
-        };
+        let maze: util::CharMaze = s.parse()?;
 
-        result.start_pos = util::Coord::from_row_major_index(start_idx, rows, cols);
-        result.end_pos = util::Coord::from_row_major_index(end_idx, rows, cols);
-
-        Ok(result)
+        Ok(Problem {
+            maze: maze.maze,
+            start_pos: maze.start_pos,
+            end_pos: maze.end_pos,
+        })
     }
 }
 
+/// Only runs the parser on the input, without solving anything. Returns an
+/// error describing the problem if the input is malformed.
+pub fn parse(input: &str) -> Result<(), util::InputError> {
+    input.parse::<Problem>().map(|_| ())
+}
+
 pub fn part_a(input: &str) -> usize {
     let problem: Problem = input.parse().unwrap();
     problem.find_cheapest_path()
 }
 
+/// Same as part_a(), but with the reindeer starting in the given direction,
+/// and optionally required to arrive at the end tile in the given direction.
+/// Returns None if the end can't be reached that way.
+pub fn cheapest_path_with_directions(
+    input: &str,
+    start_dir: util::Direction,
+    end_dir: Option<util::Direction>,
+) -> Option<usize> {
+    let problem: Problem = input.parse().unwrap();
+    problem.find_cheapest_path_with_directions(start_dir, end_dir)
+}
+
+/// Number of distinct cheapest paths through the maze.
+pub fn num_optimal_paths(input: &str) -> usize {
+    let problem: Problem = input.parse().unwrap();
+    problem.num_optimal_paths()
+}
+
 pub fn part_b(input: &str) -> usize {
     let problem: Problem = input.parse().unwrap();
     problem.find_num_path_cells()
@@ -352,4 +441,60 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn example_a_part_1_directions() {
+        util::run_test(|| {
+            let input = util::read_resource("example_16-part_1.txt").unwrap();
+            let cheapest = |start_dir, end_dir| {
+                crate::day_16::cheapest_path_with_directions(&input, start_dir, end_dir).unwrap()
+            };
+            const TURN: usize = 1000;
+
+            assert_eq!(cheapest(util::Direction::East, None), 7036);
+
+            // The cheapest path starts by heading north, so already facing that
+            // way saves a turn, while facing south requires an extra one.
+            assert_eq!(cheapest(util::Direction::North, None), 7036 - TURN);
+            assert_eq!(cheapest(util::Direction::South, None), 7036 + TURN);
+
+            // The cheapest path arrives heading north, so requiring any other
+            // direction costs at least a turn.
+            assert_eq!(
+                cheapest(util::Direction::East, Some(util::Direction::North)),
+                7036
+            );
+            assert_eq!(
+                cheapest(util::Direction::East, Some(util::Direction::East)),
+                7036 + TURN
+            );
+        });
+    }
+
+    #[test]
+    fn num_optimal_paths() {
+        util::run_test(|| {
+            let input = util::read_resource("example_16-part_1.txt").unwrap();
+            assert_eq!(crate::day_16::num_optimal_paths(&input), 3);
+
+            // Either go north first and then east, or first east, then north
+            // and then east again. Both take 5 steps and 2 turns.
+            let input = "######\n#...E#\n#.#.##\n#S...#\n######\n";
+            assert_eq!(crate::day_16::num_optimal_paths(input), 2);
+
+            assert_eq!(crate::day_16::num_optimal_paths("#####\n#S#E#\n#####\n"), 0);
+        });
+    }
+
+    #[test]
+    fn missing_end() {
+        util::run_test(|| {
+            assert_eq!(
+                "#####\n#S..#\n#####\n"
+                    .parse::<crate::day_16::Problem>()
+                    .unwrap_err(),
+                util::InputError::MissingMarker("end")
+            );
+        });
+    }
 }
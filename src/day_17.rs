@@ -174,7 +174,38 @@ impl Computer {
         true
     }
 
-    fn reversed_backtracking(&self) -> usize {
+    /// Run the program with register A set to the given value, and return the
+    /// index of the first output which differs from the program. An output
+    /// that's shorter or longer than the program differs at the index where
+    /// the shortest of both ends. Returns None if the program outputs itself.
+    fn first_mismatch(&self, a: usize) -> Option<usize> {
+        let mut state = self.state;
+        *state.get_mut(Register::A) = a;
+
+        let mut output_idx = 0;
+        let check_output = |output: u8| -> bool {
+            let output_correct = self.instructions.get(output_idx) == Some(&output);
+            if output_correct {
+                output_idx += 1;
+            }
+            output_correct
+        };
+
+        match self._run_with_callback(state, check_output) {
+            true if output_idx == self.instructions.len() => None,
+            _ => Some(output_idx),
+        }
+    }
+
+    /// Returns the lowest value for register A for which the program outputs
+    /// itself, or None if there is no such value.
+    fn reversed_backtracking(&self) -> Option<usize> {
+        self.self_replicating_values(1).first().copied()
+    }
+
+    /// Find up to limit values for register A (in ascending order) for which
+    /// the program outputs itself.
+    fn self_replicating_values(&self, limit: usize) -> Vec<usize> {
         // NOTE: This is a crappy implementation that only works for a very
         // specific input, because I couldn't get a reverse running
         // implementation to work properly.
@@ -188,10 +219,12 @@ impl Computer {
                 }
             })
             .collect();
-        assert_eq!(a_shifts.len(), 1);
 
-        // We need A to be shifted by a fixed amount.
-        assert_eq!(a_shifts[0].mapped_register(), None);
+        // We need A to be shifted by a fixed amount, exactly once per loop.
+        // Other programs aren't supported, so no solutions are found for them.
+        if a_shifts.len() != 1 || a_shifts[0].mapped_register().is_some() {
+            return Vec::new();
+        }
 
         // Find solution backwards, assuming that B & C registers are zero.
         let state = State {
@@ -199,14 +232,15 @@ impl Computer {
             registers: [0, 0, 0],
         };
         let num_bit_shifts = a_shifts[0].value(&state) as u32;
-        let mut output = Vec::new();
+        let mut solutions = Vec::new();
         self._reversed_backtracking_recurse(
             num_bit_shifts,
             state,
             self.instructions.len(),
-            &mut output,
-        )
-        .unwrap()
+            limit,
+            &mut solutions,
+        );
+        solutions
     }
 
     fn _reversed_backtracking_recurse(
@@ -214,12 +248,24 @@ impl Computer {
         num_bit_shifts: u32,
         mut state: State,
         num_outputs_remaining: usize,
-        output: &mut Vec<u8>,
-    ) -> Option<usize> {
+        limit: usize,
+        solutions: &mut Vec<usize>,
+    ) {
+        if solutions.len() >= limit {
+            return;
+        }
+
         if num_outputs_remaining == 0 {
-            return Some(state.get(Register::A));
+            // Every partial check only looks at the outputs it produces, so
+            // make sure the complete program is output exactly.
+            if self.run(state) == self.instructions {
+                solutions.push(state.get(Register::A));
+            }
+            return;
         }
 
+        // Since the lower bits are tried in ascending order, and higher bits
+        // are fixed first, solutions are found in ascending order.
         let prev_a_shifted: usize = state.get(Register::A) << num_bit_shifts;
         for a_lsbs in 0..2usize.pow(num_bit_shifts) {
             *state.get_mut(Register::A) = prev_a_shifted | a_lsbs;
@@ -228,7 +274,7 @@ impl Computer {
             // try the next option.
             let mut output_idx = num_outputs_remaining - 1;
             let check_ouput = |output: u8| -> bool {
-                let output_correct = self.instructions[output_idx] == output;
+                let output_correct = self.instructions.get(output_idx) == Some(&output);
                 output_idx += 1;
                 output_correct
             };
@@ -248,18 +294,17 @@ impl Computer {
                 continue;
             }
 
-            let next = self._reversed_backtracking_recurse(
+            self._reversed_backtracking_recurse(
                 num_bit_shifts,
                 state,
                 num_outputs_remaining - 1,
-                output,
+                limit,
+                solutions,
             );
-            if next.is_some() {
-                return next;
+            if solutions.len() >= limit {
+                return;
             }
         }
-
-        None
     }
 }
 
@@ -295,9 +340,51 @@ impl std::str::FromStr for Computer {
 }
 
 pub fn part_a(input: &str) -> String {
+    part_a_radix(input, 10)
+}
+
+/// Same as part_a(), but with each output value written in the given radix,
+/// e.g. 2 to see which bits are set. Panics if the radix isn't in 2..=36.
+pub fn part_a_radix(input: &str, radix: u32) -> String {
+    assert!((2..=36).contains(&radix), "unsupported radix: {}", radix);
+
+    let to_radix = |mut value: u32| -> String {
+        let mut digits = Vec::new();
+        loop {
+            digits.push(char::from_digit(value % radix, radix).unwrap());
+            value /= radix;
+            if value == 0 {
+                break;
+            }
+        }
+        digits.iter().rev().collect()
+    };
+
     let computer: Computer = input.parse().unwrap();
     let output = computer.run(computer.state);
-    itertools::join(output.iter().map(|e| format!("{}", e)), ",")
+    itertools::join(output.iter().map(|&e| to_radix(e as u32)), ",")
+}
+
+/// Find up to limit values for register A, in ascending order, that make the
+/// program output itself.
+pub fn self_replicating_values(input: &str, limit: usize) -> Vec<usize> {
+    let computer: Computer = input.parse().unwrap();
+    computer.self_replicating_values(limit)
+}
+
+/// Find the lowest value for register A that makes the program output itself.
+/// Returns None if there's no such value.
+pub fn lowest_self_replicating_value(input: &str) -> Option<usize> {
+    let computer: Computer = input.parse().unwrap();
+    computer.reversed_backtracking()
+}
+
+/// Index of the first output that differs from the program when it's run with
+/// register A set to the given value. Returns None if the program outputs
+/// itself. Useful to check how close a candidate value is.
+pub fn first_mismatch(input: &str, a: usize) -> Option<usize> {
+    let computer: Computer = input.parse().unwrap();
+    computer.first_mismatch(a)
 }
 
 pub fn part_b(input: &str) -> usize {
@@ -309,7 +396,9 @@ pub fn part_b(input: &str) -> usize {
             .map(|idx| format!("{:?}", computer.read_instruction(idx)))
             .join("\n")
     );
-    computer.reversed_backtracking()
+    computer
+        .reversed_backtracking()
+        .expect("no value for register A makes the program output itself")
 }
 
 #[cfg(test)]
@@ -325,6 +414,25 @@ mod tests {
         });
     }
 
+    #[test]
+    fn example_a_radix() {
+        util::run_test(|| {
+            let input = util::read_resource("example_17-part_1.txt").unwrap();
+            assert_eq!(
+                crate::day_17::part_a_radix(&input, 10),
+                crate::day_17::part_a(&input)
+            );
+            assert_eq!(
+                crate::day_17::part_a_radix(&input, 2),
+                "100,110,11,101,110,11,101,10,1,0"
+            );
+            assert_eq!(
+                crate::day_17::part_a_radix(&input, 3),
+                "11,20,10,12,20,10,12,2,1,0"
+            );
+        });
+    }
+
     #[test]
     fn example_b() {
         util::run_test(|| {
@@ -335,4 +443,61 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn example_self_replicating_values() {
+        util::run_test(|| {
+            let input = util::read_resource("example_17-part_2.txt").unwrap();
+            let computer: crate::day_17::Computer = input.parse().unwrap();
+
+            let values = crate::day_17::self_replicating_values(&input, 5);
+            assert_eq!(values[0], 117440);
+            assert!(values.windows(2).all(|e| e[0] < e[1]));
+
+            for &value in values.iter() {
+                let mut state = computer.state;
+                state.registers[0] = value;
+                assert_eq!(computer.run(state), computer.instructions);
+            }
+
+            assert!(crate::day_17::self_replicating_values(&input, 0).is_empty());
+        });
+    }
+
+    #[test]
+    fn first_mismatch() {
+        util::run_test(|| {
+            let input = util::read_resource("example_17-part_2.txt").unwrap();
+            assert_eq!(crate::day_17::first_mismatch(&input, 117440), None);
+
+            // Outputs 1, but the program starts with 0.
+            assert_eq!(crate::day_17::first_mismatch(&input, 8), Some(0));
+            // Outputs only a single 0, i.e. the second output is missing.
+            assert_eq!(crate::day_17::first_mismatch(&input, 0), Some(1));
+            // Only the highest digit is off.
+            assert_eq!(
+                crate::day_17::first_mismatch(&input, 117440 ^ (1 << 18)),
+                Some(5)
+            );
+        });
+    }
+
+    #[test]
+    fn no_self_replicating_value() {
+        util::run_test(|| {
+            // Only ever outputs 1s, which can't reproduce the program.
+            let input = "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 0,3,5,1,3,0\n";
+            assert_eq!(crate::day_17::lowest_self_replicating_value(input), None);
+
+            // Never shifts register A, so isn't supported.
+            let input = "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 5,4\n";
+            assert_eq!(crate::day_17::lowest_self_replicating_value(input), None);
+
+            let input = util::read_resource("example_17-part_2.txt").unwrap();
+            assert_eq!(
+                crate::day_17::lowest_self_replicating_value(&input),
+                Some(117440)
+            );
+        });
+    }
 }
@@ -5,25 +5,43 @@ struct Problem {
     byte_pos: Vec<util::Coord>,
 }
 
-fn from_line(line: &str) -> util::Coord {
-    let comma_pos = line.find(',').unwrap();
-    util::Coord {
-        row: line[0..comma_pos].parse().unwrap(),
-        col: line[comma_pos + 1..].parse().unwrap(),
-    }
+fn from_line(line: &str) -> Result<util::Coord, util::InputError> {
+    let (row, col) = line
+        .split_once(',')
+        .ok_or(util::InputError::MissingSeparator(','))?;
+    Ok(util::Coord {
+        row: row.parse()?,
+        col: col.parse()?,
+    })
 }
 
 impl std::str::FromStr for Problem {
-    type Err = std::string::ParseError;
+    type Err = util::InputError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Problem {
-            byte_pos: s.lines().map(|e| from_line(e)).collect(),
+            byte_pos: s.lines().map(from_line).collect::<Result<_, _>>()?,
         })
     }
 }
 
 impl Problem {
+    /// The memory space after the given number of bytes have fallen, going
+    /// from the top-left to the bottom-right corner.
+    fn maze(&self, map_size: util::Coord, cur_time: usize) -> util::Maze {
+        let mut maze =
+            na::DMatrix::from_element(map_size.row as usize, map_size.col as usize, true);
+        for pos in self.byte_pos.iter().take(cur_time) {
+            maze[pos] = false;
+        }
+
+        util::Maze {
+            maze,
+            start_pos: util::Coord { row: 0, col: 0 },
+            end_pos: map_size - util::Coord { row: 1, col: 1 },
+        }
+    }
+
     fn path_length(&self, map_size: util::Coord, cur_time: usize) -> Option<usize> {
         let start_pos = util::Coord { row: 0, col: 0 };
         let end_pos = map_size - util::Coord { row: 1, col: 1 };
@@ -86,9 +104,10 @@ fn part_b_configurable(input: &str, map_size: util::Coord) -> String {
             let time = idx + 1;
             // The partition_point function expects true, true, ..., false. It's
             // the first element causing false we're looking for.
-            let solution = problem.path_length(map_size, time);
-            log::debug!("time: {}, solution: {:?}", time, solution,);
-            solution.is_some()
+            let maze = problem.maze(map_size, time);
+            let is_reachable = maze.is_reachable(maze.start_pos, maze.end_pos);
+            log::debug!("time: {}, reachable: {}", time, is_reachable);
+            is_reachable
         });
     let coord = &problem.byte_pos[blocking_coord_idx];
     format!("{},{}", coord.row, coord.col)
@@ -138,4 +157,32 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn example_reachable() {
+        util::run_test(|| {
+            let input = util::read_resource("example_18.txt").unwrap();
+            let problem: crate::day_18::Problem = input.parse().unwrap();
+
+            // The 21st byte (at 6,1) is the first one to cut off the exit.
+            for (time, expected) in [(0, true), (12, true), (20, true), (21, false), (25, false)] {
+                let maze = problem.maze(MAP_SIZE, time);
+                assert_eq!(maze.is_reachable(maze.start_pos, maze.end_pos), expected);
+            }
+        });
+    }
+
+    #[test]
+    fn malformed_input() {
+        util::run_test(|| {
+            assert_eq!(
+                "1,2\n3 4\n".parse::<crate::day_18::Problem>().err(),
+                Some(util::InputError::MissingSeparator(','))
+            );
+            assert!(matches!(
+                "1,2\n3,x\n".parse::<crate::day_18::Problem>(),
+                Err(util::InputError::BadInteger(_))
+            ));
+        });
+    }
 }
@@ -6,34 +6,40 @@ struct Problem<'a> {
     designs: Vec<&'a [u8]>,
 }
 
-impl<'a> From<&'a str> for Problem<'a> {
-    fn from(s: &'a str) -> Self {
-        let mut lines = s.lines();
+impl<'a> TryFrom<&'a str> for Problem<'a> {
+    type Error = util::InputError;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        // The first non-blank line holds the patterns, all other non-blank ones
+        // are designs. I.e. the number of blank lines in between doesn't matter.
+        let mut lines = s.lines().map(|e| e.trim_end()).filter(|e| !e.is_empty());
+        let patterns = lines.next().ok_or(util::InputError::Empty)?;
 
         // Prepare pattern set for trie building.
         // NOTE: A value of type u32 required, so we just use zero everywhere,
         // since we're only interested in prefix lengths and we don't actually
         // need an associated value.
-        let mut pattern_set: Vec<(&[u8], u32)> = lines
-            .next()
-            .unwrap()
-            .split(", ")
+        let mut pattern_set: Vec<(&[u8], u32)> = patterns
+            .split(',')
+            .map(|e| e.trim())
+            .filter(|e| !e.is_empty())
             .map(|e| (e.as_bytes(), 0))
             .collect();
+        if pattern_set.is_empty() {
+            return Err(util::InputError::MissingMarker("towel patterns"));
+        }
 
         // Sort patterns alphabetically, required for trie building.
         pattern_set.sort_by_key(|(k, _)| -> &[u8] { k });
+        pattern_set.dedup_by_key(|(k, _)| -> &[u8] { k });
 
         // Build the trie.
         let trie_builder = yada::builder::DoubleArrayBuilder::build(&pattern_set);
 
-        Problem {
+        Ok(Problem {
             patterns: yada::DoubleArray::new(trie_builder.unwrap()),
-            designs: lines
-                .skip_while(|e| e.is_empty())
-                .map(|e| e.as_bytes())
-                .collect(),
-        }
+            designs: lines.map(|e| e.as_bytes()).collect(),
+        })
     }
 }
 
@@ -74,51 +80,64 @@ impl<'a> Problem<'a> {
         self._is_design_possible(design, 0, &offset_possible)
     }
 
-    fn _count_designs(
-        &self,
-        design: &[u8],
-        offset: usize,
-        offset_counts: &RefCell<Vec<Option<usize>>>,
-    ) -> usize {
-        if let Some(count) = offset_counts.borrow()[offset] {
-            return count;
-        }
-
-        // Sum all solutions for matching suffixes in the haystack.
-        let num_solutions = self
-            .patterns
-            .common_prefix_search(design)
-            .map(|(_, prefix_length)| {
-                self._count_designs(
-                    &design[prefix_length as usize..],
-                    offset + prefix_length as usize,
-                    offset_counts,
-                )
-            })
-            .sum();
-
-        // Cache solution.
-        offset_counts.borrow_mut()[offset] = Some(num_solutions);
-        num_solutions
+    /// Recursive version of count_designs(). Kept around for comparison, but
+    /// might recurse very deeply for long designs made of short patterns.
+    #[cfg(test)]
+    fn count_designs_recursive(&self, design: &[u8]) -> usize {
+        // Every matching prefix at a given offset leads to the offset right
+        // after it. Reaching the end of the design means a solution was found.
+        let result = util::count_paths(
+            0,
+            |&offset| offset == design.len(),
+            |&offset| {
+                self.patterns
+                    .common_prefix_search(&design[offset..])
+                    .map(|(_, prefix_length)| offset + prefix_length as usize)
+                    .collect()
+            },
+        );
+        log::debug!(
+            "# solutions for {}: {}",
+            std::str::from_utf8(design).unwrap(),
+            result
+        );
+        result
     }
 
     fn count_designs(&self, design: &[u8]) -> usize {
-        // Create cache and prime it with 1 solution for a zero length haystack.
-        let offset_counts = RefCell::new(vec![None; design.len() + 1]);
-        offset_counts.borrow_mut()[design.len()] = Some(1);
+        // Fill in the number of ways each suffix of the design can be made,
+        // from the end of the design to the front. Every matching prefix at a
+        // given offset leads to the offset right after it, whose count is
+        // already known. The zero length suffix at the end can be made in
+        // exactly one way.
+        let mut offset_counts = vec![0; design.len() + 1];
+        offset_counts[design.len()] = 1;
+
+        for offset in (0..design.len()).rev() {
+            offset_counts[offset] = self
+                .patterns
+                .common_prefix_search(&design[offset..])
+                .map(|(_, prefix_length)| offset_counts[offset + prefix_length as usize])
+                .sum();
+        }
 
-        let result = self._count_designs(design, 0, &offset_counts);
         log::debug!(
             "# solutions for {}: {}",
             std::str::from_utf8(design).unwrap(),
-            result
+            offset_counts[0]
         );
-        result
+        offset_counts[0]
     }
 }
 
+/// Only runs the parser on the input, without solving anything. Returns an
+/// error describing the problem if the input is malformed.
+pub fn parse(input: &str) -> Result<(), util::InputError> {
+    Problem::try_from(input).map(|_| ())
+}
+
 pub fn part_a(input: &str) -> usize {
-    let problem: Problem = input.into();
+    let problem = Problem::try_from(input).unwrap();
     problem
         .designs
         .iter()
@@ -127,7 +146,7 @@ pub fn part_a(input: &str) -> usize {
 }
 
 pub fn part_b(input: &str) -> usize {
-    let problem: Problem = input.into();
+    let problem = Problem::try_from(input).unwrap();
     problem
         .designs
         .iter()
@@ -158,4 +177,66 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn blank_lines_and_crlf() {
+        util::run_test(|| {
+            let input = util::read_resource("example_19.txt").unwrap();
+
+            // Windows line endings, extra blank lines everywhere and trailing
+            // whitespace after patterns and designs.
+            let messy: String = input.lines().flat_map(|e| [e, " \r\n", "\r\n"]).collect();
+            let messy = format!("\r\n{}\r\n", messy);
+
+            let problem = crate::day_19::Problem::try_from(messy.as_str()).unwrap();
+            let expected = crate::day_19::Problem::try_from(input.as_str()).unwrap();
+            assert_eq!(problem.designs, expected.designs);
+            assert_eq!(problem.designs.len(), 8);
+            assert_eq!(problem.designs[0], b"brwrr");
+
+            assert_eq!(crate::day_19::part_a(&messy), 6);
+            assert_eq!(crate::day_19::part_b(&messy), 16);
+        });
+    }
+
+    #[test]
+    fn iterative_vs_recursive_count() {
+        util::run_test(|| {
+            let input = util::read_resource("example_19.txt").unwrap();
+            let problem = crate::day_19::Problem::try_from(input.as_str()).unwrap();
+            for design in problem.designs.iter() {
+                assert_eq!(
+                    problem.count_designs(design),
+                    problem.count_designs_recursive(design)
+                );
+            }
+
+            // Every run of "r"s can be split into any combination of "r" and
+            // "rr", i.e. the counts are Fibonacci numbers.
+            let problem = crate::day_19::Problem::try_from("r, rr, b\n").unwrap();
+            let design = "r".repeat(80);
+            let count = problem.count_designs(design.as_bytes());
+            assert_eq!(count, 37889062373143906);
+            assert_eq!(count, problem.count_designs_recursive(design.as_bytes()));
+
+            // Each "rr" can be made in two ways, the trailing "w" in none.
+            let design = "rrb".repeat(20);
+            assert_eq!(problem.count_designs(design.as_bytes()), 1 << 20);
+            let design = format!("{}w", "rrb".repeat(1000));
+            assert_eq!(problem.count_designs(design.as_bytes()), 0);
+        });
+    }
+
+    #[test]
+    fn missing_patterns() {
+        util::run_test(|| {
+            for input in ["", "\n\n", " \r\n"] {
+                assert_eq!(crate::day_19::parse(input), Err(util::InputError::Empty));
+            }
+            assert_eq!(
+                crate::day_19::parse(", ,\n\nbrwrr\n"),
+                Err(util::InputError::MissingMarker("towel patterns"))
+            );
+        });
+    }
 }
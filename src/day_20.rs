@@ -1,31 +1,29 @@
 use nalgebra as na;
 use rayon::prelude::*;
-use std::simd::{cmp::SimdPartialOrd, num::SimdInt, Simd};
+use std::collections::BTreeMap;
+#[cfg(feature = "simd")]
+use std::simd::{cmp::SimdPartialOrd, Simd};
 
-struct Problem {
+pub struct Problem {
     maze: util::Maze,
 }
 
 impl std::str::FromStr for Problem {
-    type Err = std::string::ParseError;
+    type Err = util::InputError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Problem {
-            maze: s.parse().unwrap(),
-        })
+        let maze: util::Maze = s.parse()?;
+
+        // The race track must be a single path, without any side branches.
+        util::trace_single_path(&maze, maze.end_pos, maze.start_pos)?;
+
+        Ok(Problem { maze })
     }
 }
 
-// NOTE: Rust doesn't allow statics in a struct's impl, so here we are.
-static SEARCH_DIRS: [util::Coord; 4] = [
-    util::Direction::North.to_coord(),
-    util::Direction::East.to_coord(),
-    util::Direction::South.to_coord(),
-    util::Direction::West.to_coord(),
-];
-
 impl Problem {
-    const SIMD_SIZE: usize = 16;
+    #[cfg(feature = "simd")]
+    const SIMD_LANES: usize = util::simd_lanes(16);
 
     /// Calculate distance from any point on the race track to the end point.
     /// Returns the coordinates of the race track (in reverse order, i.e. from
@@ -36,48 +34,39 @@ impl Problem {
         // later on to avoid having to call saturating_add() on SIMD elements.
         let unreachable_value = (maze.size().row * maze.size().col + 1) as u16;
 
-        let mut reversed_path = Vec::new();
         let mut distances = na::DMatrix::from_element(
             maze.size().row as usize,
             maze.size().col as usize,
             unreachable_value,
         );
 
-        distances[maze.end_pos] = 0;
-        reversed_path.push(maze.end_pos);
-
         // NOTE: The race track is exactly that: a track, i.e. there's no side
         // branches or anything, just a single path.
-        let mut cur_pos = maze.end_pos;
-
-        // Do a BFS until each (reachable) cell has been visited.
-        while cur_pos != maze.start_pos {
-            // Only one of these directions is possible. It's the cell that's
-            // not a wall, and not yet visited.
-            cur_pos = SEARCH_DIRS
-                .iter()
-                .map(|offset| cur_pos + offset)
-                .filter(|pos| maze.accessible(pos) && (distances[pos] == unreachable_value))
-                .next()
-                .unwrap();
-
-            distances[cur_pos] = reversed_path.len() as u16;
-            reversed_path.push(cur_pos);
+        let reversed_path = util::trace_single_path(maze, maze.end_pos, maze.start_pos)
+            .expect("race track is checked when parsing");
+        for (distance, pos) in reversed_path.iter().enumerate() {
+            distances[pos] = distance as u16;
         }
 
         assert_eq!(reversed_path.len() - 1, distances[maze.start_pos] as usize);
         (reversed_path, distances)
     }
 
+    #[cfg(feature = "simd")]
     fn _num_masks_per_column(max_cheat_distance: u16) -> usize {
         (2 * max_cheat_distance + 1) as usize
     }
 
-    fn _num_simd_words_per_column(max_cheat_distance: u16) -> usize {
-        Self::_num_masks_per_column(max_cheat_distance).div_ceil(Self::SIMD_SIZE)
+    #[cfg(feature = "simd")]
+    fn _num_simd_words_per_column<const LANES: usize>(max_cheat_distance: u16) -> usize {
+        Self::_num_masks_per_column(max_cheat_distance).div_ceil(LANES)
     }
 
-    fn _expand_maze(prev_maze: &util::Maze, max_cheat_distance: u16) -> util::Maze {
+    #[cfg(feature = "simd")]
+    fn _expand_maze<const LANES: usize>(
+        prev_maze: &util::Maze,
+        max_cheat_distance: u16,
+    ) -> util::Maze {
         // Expand maze matrix, such that we never have to check for bounds.
         let maze_offset = util::Coord {
             row: max_cheat_distance as isize,
@@ -92,7 +81,7 @@ impl Problem {
         // bottom-most cell in the original maze there is guaranteed no
         // out-of-bounds access when loading all "south" cells in SIMD elements.
         let maze_expansion: (usize, usize) = (
-            Self::SIMD_SIZE * Self::_num_simd_words_per_column(max_cheat_distance),
+            LANES * Self::_num_simd_words_per_column::<LANES>(max_cheat_distance),
             2 * max_cheat_distance as usize,
         );
         let mut expanded_maze = util::Maze {
@@ -114,10 +103,11 @@ impl Problem {
         expanded_maze
     }
 
-    fn _calculate_simd_masks(
+    #[cfg(feature = "simd")]
+    fn _calculate_simd_masks<const LANES: usize>(
         &self,
         max_cheat_distance: u16,
-    ) -> Vec<Vec<Simd<u16, { Self::SIMD_SIZE }>>> {
+    ) -> Vec<Vec<Simd<u16, LANES>>> {
         // Calculate SIMD masks for all the accessible cheat endpoints from a
         // given start point. Each entry in the mask contains its distance from
         // the origin point (Manhattan distance). Unreachable points are set to
@@ -133,10 +123,11 @@ impl Problem {
             // the left. For the next columns, an extra cell is reachable above
             // and below the previously "highest" & "lowest" reachable cell. At
             // column 0, all cells in cheat distance are reachable.
-            let mut column_masks = vec![
-                Simd::splat(max_path_length);
-                Self::_num_simd_words_per_column(max_cheat_distance)
-            ];
+            let mut column_masks =
+                vec![
+                    Simd::splat(max_path_length);
+                    Self::_num_simd_words_per_column::<LANES>(max_cheat_distance)
+                ];
 
             for row in -(max_cheat_distance as i16)..=(max_cheat_distance as i16) {
                 let offset = util::Coord {
@@ -150,8 +141,8 @@ impl Problem {
                 }
 
                 let offset_row = (row + max_cheat_distance as i16) as usize;
-                let element_idx = offset_row / Self::SIMD_SIZE;
-                let word_idx = offset_row % Self::SIMD_SIZE;
+                let element_idx = offset_row / LANES;
+                let word_idx = offset_row % LANES;
 
                 column_masks[element_idx][word_idx] = dist_from_center;
             }
@@ -163,75 +154,173 @@ impl Problem {
         simd_masks
     }
 
-    fn num_cheat_paths(&self, min_required_improvement: u16, max_cheat_distance: u16) -> u64 {
+    /// Same as cheat_savings(), but without any SIMD trickery. Useful as a
+    /// reference, but slower.
+    fn _cheat_savings_scalar(
+        &self,
+        min_time_saving: u16,
+        max_cheat_distance: u16,
+    ) -> BTreeMap<u16, u64> {
+        let (reversed_path, dist_from_end) = Self::calculate_race_path(&self.maze);
+
+        let savings = reversed_path[min_time_saving as usize..]
+            .par_iter()
+            .fold(
+                || vec![0; reversed_path.len()],
+                |mut savings, pos| {
+                    for (cheat_end, cheat_distance) in pos.within_manhattan(max_cheat_distance) {
+                        if !self.maze.accessible(&cheat_end) {
+                            continue;
+                        }
+
+                        let cheated_dist_to_end = dist_from_end[cheat_end] + cheat_distance;
+                        if cheated_dist_to_end + min_time_saving <= dist_from_end[pos] {
+                            savings[(dist_from_end[pos] - cheated_dist_to_end) as usize] += 1;
+                        }
+                    }
+                    savings
+                },
+            )
+            .reduce(|| vec![0; reversed_path.len()], Self::_add_savings);
+
+        Self::_savings_to_histogram(savings)
+    }
+
+    /// Merge the time saving counts of two parts of the race path.
+    fn _add_savings(mut lhs: Vec<u64>, rhs: Vec<u64>) -> Vec<u64> {
+        lhs.iter_mut().zip(rhs).for_each(|(lhs, rhs)| *lhs += rhs);
+        lhs
+    }
+
+    /// Convert time saving counts, indexed by time saving, into a histogram.
+    fn _savings_to_histogram(savings: Vec<u64>) -> BTreeMap<u16, u64> {
+        savings
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, count)| count > 0)
+            .map(|(saving, count)| (saving as u16, count))
+            .collect()
+    }
+
+    /// Count how many cheats of at most the given distance result in each
+    /// possible time saving of at least min_time_saving (which must be strictly
+    /// positive).
+    fn cheat_savings(&self, min_time_saving: u16, max_cheat_distance: u16) -> BTreeMap<u16, u64> {
+        assert!(min_time_saving > 0);
+
+        #[cfg(feature = "simd")]
+        return self
+            ._cheat_savings_simd::<{ Self::SIMD_LANES }>(min_time_saving, max_cheat_distance);
+        #[cfg(not(feature = "simd"))]
+        return self._cheat_savings_scalar(min_time_saving, max_cheat_distance);
+    }
+
+    #[cfg(feature = "simd")]
+    fn _cheat_savings_simd<const LANES: usize>(
+        &self,
+        min_time_saving: u16,
+        max_cheat_distance: u16,
+    ) -> BTreeMap<u16, u64> {
         assert!(self.maze.maze.nrows() < 255);
         assert!(self.maze.maze.ncols() < 255);
 
         // Expand maze matrix, such that we never have to check for bounds.
-        let expanded_maze = Self::_expand_maze(&self.maze, max_cheat_distance);
+        let expanded_maze = Self::_expand_maze::<LANES>(&self.maze, max_cheat_distance);
 
         let (reversed_path, dist_from_end) = Self::calculate_race_path(&expanded_maze);
-        let distance_masks = self._calculate_simd_masks(max_cheat_distance);
+        let distance_masks = self._calculate_simd_masks::<LANES>(max_cheat_distance);
 
         // Loop over every step of the race path.
-        // NOTE: Paths closer to the end than the minimum required improvement
-        // can't improve enough on the solution, so skip those.
-        reversed_path[min_required_improvement as usize..]
+        // NOTE: Paths closer to the end than the minimum time saving can't
+        // improve enough on the solution, so skip those.
+        let savings = reversed_path[min_time_saving as usize..]
             .into_par_iter()
-            .map(|pos| {
-                let max_dist_to_end = Simd::splat(dist_from_end[pos] - min_required_improvement);
-                let cheat_start_row = pos.row as usize - max_cheat_distance as usize;
-
-                let mut num_valid_cheats = Simd::splat(0);
-
-                // Process every column in the jump masks table.
-                let columns = dist_from_end.columns(
-                    (pos.col - max_cheat_distance as isize) as usize,
-                    (2 * max_cheat_distance + 1) as usize,
-                );
-                for (column_masks, column) in distance_masks.iter().zip(columns.column_iter()) {
-                    let col_slice = &column.as_slice()[cheat_start_row..];
-
-                    for (cheat_distances, dists_chunk) in
-                        column_masks.iter().zip(col_slice.chunks(Self::SIMD_SIZE))
-                    {
-                        let dists_to_end = Simd::from_slice(&dists_chunk);
-                        let cheated_dist_to_end = dists_to_end + cheat_distances;
-                        let is_valid_cheat = cheated_dist_to_end.simd_le(max_dist_to_end);
-
-                        // An SIMD Mask converted to Simd has 0 for false and -1
-                        // for true. So subtract to get the equivalent of adding
-                        // a 1 for each matching comparison.
-                        // NOTE: This is a loop-carried dependency. Trying to get
-                        // rid of it by summing into an intermediate array slows
-                        // things down by a factor of 30% though.
-                        // The compiler seems to unroll this loop by 2 such that
-                        // the loop-carried dependency doesn't slow things down.
-                        num_valid_cheats -= is_valid_cheat.to_int();
+            .fold(
+                || vec![0; reversed_path.len()],
+                |mut savings, pos| {
+                    let dist_to_end = dist_from_end[pos];
+                    let max_dist_to_end = Simd::splat(dist_to_end - min_time_saving);
+                    let cheat_start_row = pos.row as usize - max_cheat_distance as usize;
+
+                    // Process every column in the jump masks table.
+                    let columns = dist_from_end.columns(
+                        (pos.col - max_cheat_distance as isize) as usize,
+                        (2 * max_cheat_distance + 1) as usize,
+                    );
+                    for (column_masks, column) in distance_masks.iter().zip(columns.column_iter()) {
+                        let col_slice = &column.as_slice()[cheat_start_row..];
+
+                        for (cheat_distances, dists_chunk) in
+                            column_masks.iter().zip(col_slice.chunks(LANES))
+                        {
+                            let dists_to_end = Simd::from_slice(&dists_chunk);
+                            let cheated_dist_to_end = dists_to_end + cheat_distances;
+                            let is_valid_cheat = cheated_dist_to_end.simd_le(max_dist_to_end);
+
+                            // Most cheat endpoints aren't valid, so only visit
+                            // the lanes that are set in the mask.
+                            let mut valid_lanes = is_valid_cheat.to_bitmask();
+                            while valid_lanes != 0 {
+                                let lane = valid_lanes.trailing_zeros() as usize;
+                                savings[(dist_to_end - cheated_dist_to_end[lane]) as usize] += 1;
+                                valid_lanes &= valid_lanes - 1;
+                            }
+                        }
                     }
-                }
+                    savings
+                },
+            )
+            .reduce(|| vec![0; reversed_path.len()], Self::_add_savings);
 
-                num_valid_cheats.reduce_sum() as u64
-            })
-            .sum()
+        Self::_savings_to_histogram(savings)
     }
 }
 
+#[cfg(test)]
 fn solve_configurable(input: &str, min_time_saving: u16, max_cheat_time: u16) -> u64 {
-    let problem: Problem = input.parse().unwrap();
-    problem.num_cheat_paths(min_time_saving, max_cheat_time)
+    parse(input)
+        .cheat_savings(min_time_saving, max_cheat_time)
+        .values()
+        .sum()
 }
 
-pub fn part_a(input: &str) -> u64 {
+/// Parse the input, such that it can be solved separately afterwards.
+pub fn parse(input: &str) -> Problem {
+    input.parse().unwrap()
+}
+
+/// Map each achievable time saving to the number of distinct cheats of at most
+/// max_cheat_time picoseconds that achieve it. Summing all entries from a given
+/// saving onwards gives the same answer as part_a() or part_b() would for that
+/// minimum time saving.
+pub fn cheat_savings_histogram(input: &str, max_cheat_time: u16) -> BTreeMap<u16, u64> {
+    parse(input).cheat_savings(1, max_cheat_time)
+}
+
+pub fn solve_part_a(problem: &Problem) -> u64 {
     const MIN_TIME_SAVING: u16 = 100;
     const MAX_CHEAT_TIME: u16 = 2;
-    solve_configurable(input, MIN_TIME_SAVING, MAX_CHEAT_TIME)
+    problem
+        .cheat_savings(MIN_TIME_SAVING, MAX_CHEAT_TIME)
+        .values()
+        .sum()
 }
 
-pub fn part_b(input: &str) -> u64 {
+pub fn solve_part_b(problem: &Problem) -> u64 {
     const MIN_TIME_SAVING: u16 = 100;
     const MAX_CHEAT_TIME: u16 = 20;
-    solve_configurable(input, MIN_TIME_SAVING, MAX_CHEAT_TIME)
+    problem
+        .cheat_savings(MIN_TIME_SAVING, MAX_CHEAT_TIME)
+        .values()
+        .sum()
+}
+
+pub fn part_a(input: &str) -> u64 {
+    solve_part_a(&parse(input))
+}
+
+pub fn part_b(input: &str) -> u64 {
+    solve_part_b(&parse(input))
 }
 
 #[cfg(test)]
@@ -247,6 +336,31 @@ mod tests {
         });
     }
 
+    #[test]
+    fn example_single_track() {
+        util::run_test(|| {
+            let input = util::read_resource("example_20.txt").unwrap();
+            let maze: util::Maze = input.parse().unwrap();
+            let path = util::trace_single_path(&maze, maze.start_pos, maze.end_pos).unwrap();
+            assert_eq!(path.len(), 84 + 1);
+        });
+    }
+
+    #[test]
+    fn branching_track() {
+        util::run_test(|| {
+            // The track is traced back from the end, which has two ways out.
+            let input = "#######\n#S....#\n#.##..#\n#....E#\n#######\n";
+            assert_eq!(
+                input.parse::<crate::day_20::Problem>().err(),
+                Some(util::InputError::BranchingTrack(util::Coord {
+                    row: 3,
+                    col: 5
+                }))
+            );
+        });
+    }
+
     #[test]
     fn example_a() {
         util::run_test(|| {
@@ -287,6 +401,80 @@ mod tests {
         };
     }
 
+    #[test]
+    #[cfg(feature = "simd")]
+    fn example_scalar_matches_simd() {
+        util::run_test(|| {
+            let problem = crate::day_20::parse(&util::read_resource("example_20.txt").unwrap());
+            for (min_time_saving, max_cheat_time) in [(20, 2), (2, 2), (50, 20), (76, 20)] {
+                let expected = problem._cheat_savings_scalar(min_time_saving, max_cheat_time);
+                assert_eq!(
+                    problem.cheat_savings(min_time_saving, max_cheat_time),
+                    expected
+                );
+
+                // The result doesn't depend on the number of SIMD lanes.
+                assert_eq!(
+                    problem._cheat_savings_simd::<8>(min_time_saving, max_cheat_time),
+                    expected
+                );
+                assert_eq!(
+                    problem._cheat_savings_simd::<64>(min_time_saving, max_cheat_time),
+                    expected
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn example_savings_histogram() {
+        util::run_test(|| {
+            let input = util::read_resource("example_20.txt").unwrap();
+
+            let histogram = crate::day_20::cheat_savings_histogram(&input, 2);
+            let expected = [
+                (2, 14),
+                (4, 14),
+                (6, 2),
+                (8, 4),
+                (10, 2),
+                (12, 3),
+                (20, 1),
+                (36, 1),
+                (38, 1),
+                (40, 1),
+                (64, 1),
+            ];
+            assert_eq!(histogram.into_iter().collect::<Vec<_>>(), expected);
+
+            let histogram = crate::day_20::cheat_savings_histogram(&input, 20);
+            let expected = [
+                (50, 32),
+                (52, 31),
+                (54, 29),
+                (56, 39),
+                (58, 25),
+                (60, 23),
+                (62, 20),
+                (64, 19),
+                (66, 12),
+                (68, 14),
+                (70, 12),
+                (72, 22),
+                (74, 4),
+                (76, 3),
+            ];
+            assert_eq!(
+                histogram
+                    .range(50..)
+                    .map(|(k, v)| (*k, *v))
+                    .collect::<Vec<_>>(),
+                expected
+            );
+            assert_eq!(histogram.range(50..).map(|(_, v)| v).sum::<u64>(), 285);
+        });
+    }
+
     make_example_b_test!(subset_1, 76, 3);
     make_example_b_test!(subset_2, 74, 7);
     make_example_b_test!(subset_3, 72, 29);
@@ -293,6 +293,13 @@ impl SequenceFinder {
         }
     }
 
+    /// Returns the number of filled cache entries, and the total number of
+    /// entries, i.e. for all keypads used so far.
+    fn cache_stats(&self) -> (usize, usize) {
+        let entries = || self.solution_cache.iter().flatten().flatten();
+        (entries().filter(|e| e.is_some()).count(), entries().count())
+    }
+
     fn _find_shortest_path_permutation_on_directional_keypads(
         &mut self,
         remaining_direction_keypads: usize,
@@ -314,10 +321,8 @@ impl SequenceFinder {
                     .iter()
                     .map(|permuted_path| {
                         // Iterate over each adjacent pair of keys in the path.
-                        permuted_path
-                            .iter()
-                            .tuple_windows()
-                            .map(|(&from, &to)| {
+                        util::pairs(permuted_path)
+                            .map(|(from, to)| {
                                 self._search_shortest_path_on_directional_keypads(
                                     remaining_direction_keypads - 1,
                                     DirectionKeypad::to_coord(from),
@@ -397,7 +402,24 @@ impl SequenceFinder {
     }
 }
 
-pub fn shortest_chained_sequence(line: &str, num_direction_keypads: u8) -> u64 {
+impl Drop for SequenceFinder {
+    fn drop(&mut self) {
+        let (num_filled, num_total) = self.cache_stats();
+        log::debug!("# cache entries: {} / {}", num_filled, num_total);
+    }
+}
+
+pub fn shortest_chained_sequence(line: &str, num_direction_keypads: usize) -> u64 {
+    shortest_chained_sequence_with(&mut SequenceFinder::new(), line, num_direction_keypads)
+}
+
+/// Same as shortest_chained_sequence(), but reuses the given solver. Its cache
+/// doesn't depend on the code being typed, so can be shared across lines.
+fn shortest_chained_sequence_with(
+    solver: &mut SequenceFinder,
+    line: &str,
+    num_direction_keypads: usize,
+) -> u64 {
     // Convert ASCII buttons to button indices.
     log::debug!("Line: {}", line);
     let buttons: ArrayVec<KeypadButton, 4> = line
@@ -409,37 +431,69 @@ pub fn shortest_chained_sequence(line: &str, num_direction_keypads: u8) -> u64 {
     // Chain one path finding operations per keypad. I.e. find the shortest path
     // for the given keypad, then find the shortest path to create that path
     // with the next keypad, etc.
-    let mut solver = SequenceFinder::new();
-
-    let solution = solver.shortest_sequence_length(num_direction_keypads as usize, &buttons);
+    let solution = solver.shortest_sequence_length(num_direction_keypads, &buttons);
     log::debug!("[{}] shortest path: {}", line, solution);
     solution
 }
 
-pub fn solve(input: &str, num_direction_keypads: u8) -> u64 {
-    // NOTE: Running this in parallel is slightly slower.
+/// Returns each code in the input, paired with the length of the shortest
+/// button sequence that types it when `num_direction_keypads` robot-operated
+/// directional keypads are chained in front of the numeric keypad.
+pub fn line_lengths(input: &str, num_direction_keypads: usize) -> Vec<(String, u64)> {
+    let mut solver = SequenceFinder::new();
+
     input
         .lines()
-        .map(|line| (line, shortest_chained_sequence(line, num_direction_keypads)))
-        .map(|(line, num_presses)| {
-            let first_non_zero = line.find(|e| ('0'..='9').contains(&e)).unwrap();
-            let first_last_digit = line
-                .find(|e| !('0'..='9').contains(&e))
-                .unwrap_or(line.len());
-            let value: u64 = line[first_non_zero..first_last_digit].parse().unwrap();
-            let complexity = value * num_presses;
-            log::debug!("[{}] {} * {} = {}", line, value, num_presses, complexity);
-            complexity
+        .map(|line| {
+            (
+                line.to_string(),
+                shortest_chained_sequence_with(&mut solver, line, num_direction_keypads),
+            )
         })
+        .collect()
+}
+
+/// The complexity of a code, given the length of the shortest button sequence
+/// that types it.
+fn complexity(line: &str, num_presses: u64) -> u64 {
+    let first_non_zero = line.find(|e| ('0'..='9').contains(&e)).unwrap();
+    let first_last_digit = line
+        .find(|e| !('0'..='9').contains(&e))
+        .unwrap_or(line.len());
+    let value: u64 = line[first_non_zero..first_last_digit].parse().unwrap();
+    let complexity = value * num_presses;
+    log::debug!("[{}] {} * {} = {}", line, value, num_presses, complexity);
+    complexity
+}
+
+/// Returns the complexity of a single code, i.e. its numeric part times the
+/// length of the shortest button sequence that types it when
+/// `num_direction_keypads` robot-operated directional keypads are chained in
+/// front of the numeric keypad.
+pub fn code_complexity(line: &str, num_direction_keypads: usize) -> u64 {
+    complexity(line, shortest_chained_sequence(line, num_direction_keypads))
+}
+
+/// Returns the sum of the complexities of all codes in the input, see
+/// code_complexity().
+pub fn total_complexity(input: &str, num_direction_keypads: usize) -> u64 {
+    // NOTE: Running this in parallel is slightly slower.
+    line_lengths(input, num_direction_keypads)
+        .into_iter()
+        .map(|(line, num_presses)| complexity(&line, num_presses))
         .sum()
 }
 
+pub fn solve(input: &str, num_direction_keypads: u8) -> u64 {
+    total_complexity(input, num_direction_keypads as usize)
+}
+
 pub fn part_a(input: &str) -> u64 {
-    solve(input, 2)
+    total_complexity(input, 2)
 }
 
 pub fn part_b(input: &str) -> u64 {
-    solve(input, 25)
+    total_complexity(input, 25)
 }
 
 #[cfg(test)]
@@ -504,6 +558,69 @@ mod tests {
         "<v<A>>^AvA^A<vA<AA>>^AAvA<^A>AAvA^A<vA>^AA<A>A<v<A>A>^AAAvA<^A>A".len()
     );
 
+    #[test]
+    fn example_line_lengths() {
+        util::run_test(|| {
+            let input = "029A\n980A\n179A\n456A\n379A\n";
+            let expected: Vec<(String, u64)> = [
+                ("029A", 68),
+                ("980A", 60),
+                ("179A", 68),
+                ("456A", 64),
+                ("379A", 64),
+            ]
+            .iter()
+            .map(|(code, length)| (code.to_string(), *length))
+            .collect();
+            assert_eq!(crate::day_21::line_lengths(input, 2), expected);
+        });
+    }
+
+    #[test]
+    fn example_shared_solver() {
+        util::run_test(|| {
+            let input = "029A\n980A\n179A\n456A\n379A\n";
+            let expected: u64 = 126384;
+
+            // Each line with its own solver.
+            let per_line: u64 = input
+                .lines()
+                .map(|line| crate::day_21::code_complexity(line, 2))
+                .sum();
+            assert_eq!(per_line, expected);
+
+            // One solver shared by all lines.
+            assert_eq!(crate::day_21::total_complexity(input, 2), expected);
+        });
+    }
+
+    #[test]
+    fn cache_stats() {
+        util::run_test(|| {
+            let mut solver = crate::day_21::SequenceFinder::new();
+            assert_eq!(solver.cache_stats(), (0, 0));
+
+            crate::day_21::shortest_chained_sequence_with(&mut solver, "029A", 2);
+            let (num_filled, num_total) = solver.cache_stats();
+            assert_eq!(num_total, 2 * 5 * 5);
+            assert!(num_filled > 0);
+            assert!(num_filled <= num_total);
+
+            // Typing the same code again doesn't need any new entries.
+            crate::day_21::shortest_chained_sequence_with(&mut solver, "029A", 2);
+            assert_eq!(solver.cache_stats(), (num_filled, num_total));
+        });
+    }
+
+    #[test]
+    fn example_code_complexity() {
+        util::run_test(|| {
+            assert_eq!(crate::day_21::code_complexity("029A", 2), 68 * 29);
+            assert_eq!(crate::day_21::code_complexity("980A", 2), 60 * 980);
+            assert_eq!(crate::day_21::code_complexity("4", 3), 65 * 4);
+        });
+    }
+
     // Extra examples from https://www.reddit.com/r/adventofcode/comments/1hj6o1j/comment/m347iul.
     make_example_chain_test!(extra_code_1, "159A", 82);
     make_example_chain_test!(extra_code_2, "375A", 70);
@@ -609,5 +726,15 @@ mod tests {
         });
     }
 
+    #[test]
+    fn example_total_complexity() {
+        util::run_test(|| {
+            let input = util::read_resource("example_21.txt").unwrap();
+            let expected: u64 = 126384;
+            assert_eq!(crate::day_21::total_complexity(&input, 2), expected);
+            assert_eq!(crate::day_21::part_a(&input), expected);
+        });
+    }
+
     // No example for part B.
 }
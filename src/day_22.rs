@@ -1,8 +1,7 @@
 use rayon::prelude::*;
-use std::{
-    simd::{num::*, *},
-    sync::{Arc, LazyLock, Mutex},
-};
+#[cfg(feature = "simd")]
+use std::simd::{num::*, *};
+use std::sync::{Arc, LazyLock, Mutex};
 
 #[derive(Clone, Copy, Debug)]
 struct Sequence(u32);
@@ -29,38 +28,17 @@ impl Sequence {
     }
 }
 
-struct Window {
-    value: u32,
-}
-
-impl Window {
-    const BASE: usize = 19;
-    const LENGTH: usize = 4;
-    const NUM_ENCODED_INDICES: usize = Self::BASE.pow(Self::LENGTH as u32);
-
-    fn new() -> Window {
-        Window { value: 0 }
-    }
-
-    /// Push a new value into the window.
-    /// Returns the encoding for the window.
-    fn push_and_encode(&mut self, diff: i8) -> u32 {
-        // Since a difference is between -9 to 9, there's only 19 possible
-        // values. Hence we can encode a 4-element window as a 32 bit value.
-        // This also ensures all possible windows are encoded into a contiguous
-        // range, which means we can use e.g. a Vec to map them. There's approx.
-        // 130k, so no issue storage-wise (around half a MiB if storing u32).
-
-        assert!((-9..=9).contains(&diff));
-        self.value %= Self::BASE.pow((Self::LENGTH - 1) as u32) as u32; // Remove "MSB".
-        self.value *= Self::BASE as u32; // Shift all "digits" one place up.
-        self.value += (diff + 9) as u32;
-
-        // NOTE: This assert doesn't affect benchmarked time.
-        assert!(self.value < Self::NUM_ENCODED_INDICES as u32);
-
-        self.value
-    }
+// Since a difference is between -9 to 9, there's only 19 possible values.
+// Hence we can encode a 4-element window as a 32 bit value. This also ensures
+// all possible windows are encoded into a contiguous range, which means we can
+// use e.g. a Vec to map them. There's approx. 130k, so no issue storage-wise
+// (around half a MiB if storing u32).
+pub type Window = util::SlidingBase<19, 4>;
+
+/// Returns the four price differences (oldest first) that were encoded into
+/// the given window index.
+pub fn decode_window(index: u32) -> [i8; Window::LENGTH] {
+    Window::decode(index).map(|digit| digit as i8 - 9)
 }
 
 #[allow(dead_code)]
@@ -261,7 +239,7 @@ impl Tables {
 
         log::debug!(
             "Finished part A table generation in {:.3} ms",
-            (std::time::Instant::now() - time_start).as_millis_f32()
+            (std::time::Instant::now() - time_start).as_secs_f32() * 1e3
         );
 
         result.into_boxed_slice()
@@ -291,7 +269,7 @@ impl Tables {
             let bananas = secret.bananas();
             let diff: i8 = (bananas as i8) - (prev_bananas as i8);
 
-            window.push_and_encode(diff);
+            window.push_and_encode((diff + 9) as u32);
             prev_bananas = bananas;
         }
 
@@ -309,7 +287,7 @@ impl Tables {
             secret = secret.next();
             let bananas = secret.bananas();
             let diff: i8 = (bananas as i8) - (prev_bananas as i8);
-            let window_idx = window.push_and_encode(diff);
+            let window_idx = window.push_and_encode((diff + 9) as u32);
             prev_bananas = bananas;
 
             // Ensure index points to the table's first LFSR_LENGTH entries.
@@ -355,7 +333,7 @@ impl Tables {
 
         log::debug!(
             "Finished part B table generation in {:.3} ms",
-            (std::time::Instant::now() - time_start).as_millis_f32()
+            (std::time::Instant::now() - time_start).as_secs_f32() * 1e3
         );
 
         (start_idxes.into_boxed_slice(), info.into_boxed_slice())
@@ -422,13 +400,55 @@ impl MarketStateBuilder {
     }
 }
 
-pub fn part_a(input: &str) -> u64 {
+#[derive(Debug)]
+pub struct Problem {
+    seeds: Vec<u32>,
+}
+
+/// Parse the input, such that it can be solved separately afterwards.
+pub fn parse(input: &str) -> Problem {
     // Gather all starting seeds in a Vec first, to allow chunking them up in
     // parallel afterwards.
-    let seeds: Vec<u32> = input
-        .lines()
-        .map(|e| -> u32 { e.parse().unwrap() })
-        .collect();
+    Problem {
+        seeds: input
+            .lines()
+            .map(|e| -> u32 { e.parse().unwrap() })
+            .collect(),
+    }
+}
+
+#[cfg(feature = "simd")]
+fn sum_advanced_secrets_simd<const LANES: usize>(seeds: &[u32], num_workers: usize) -> u64 {
+    let seeds_iter = seeds.par_chunks_exact(LANES);
+
+    let mut sum: u64 = seeds_iter
+        .remainder()
+        .iter()
+        .map(|secret| TABLES.advanced_secrets[*secret as usize] as u64)
+        .sum();
+
+    sum += seeds_iter
+        .with_min_len(seeds.len().div_ceil(LANES) / num_workers)
+        .map(|chunk| {
+            let idxes = Simd::from_slice(chunk);
+            unsafe {
+                Simd::gather_select_unchecked(
+                    TABLES.advanced_secrets.as_ref(),
+                    Mask::splat(true),
+                    idxes.cast(),
+                    Simd::splat(0),
+                )
+            }
+            .cast()
+        })
+        .sum::<Simd<u64, LANES>>()
+        .reduce_sum();
+
+    sum
+}
+
+pub fn solve_part_a(problem: &Problem) -> u64 {
+    let seeds = &problem.seeds;
 
     // NOTE: Sorting all seeds, such that table lookups would hopefully hit more
     // of the cache, doesn't improve runtime.
@@ -436,44 +456,18 @@ pub fn part_a(input: &str) -> u64 {
     // Used to prevent rayon from spawning 1000s of jobs.
     let num_workers: usize = std::thread::available_parallelism().unwrap().get();
 
-    const USE_SIMD: bool = true;
-    const SIMD_SIZE: usize = 8; // Tiny bit faster than 4 or 16.
+    #[cfg(not(feature = "simd"))]
+    let sum = seeds
+        .par_iter()
+        .with_min_len(seeds.len().div_ceil(num_workers))
+        .map(|secret| TABLES.advanced_secrets[*secret as usize] as u64)
+        .sum();
 
-    match USE_SIMD {
-        false => seeds
-            .par_iter()
-            .with_min_len(seeds.len().div_ceil(num_workers))
-            .map(|secret| TABLES.advanced_secrets[*secret as usize] as u64)
-            .sum(),
-        true => {
-            let seeds_iter = seeds.par_chunks_exact(SIMD_SIZE);
-
-            let mut sum: u64 = seeds_iter
-                .remainder()
-                .iter()
-                .map(|secret| TABLES.advanced_secrets[*secret as usize] as u64)
-                .sum();
-
-            sum += seeds_iter
-                .with_min_len(seeds.len().div_ceil(SIMD_SIZE) / num_workers)
-                .map(|chunk| {
-                    let idxes = Simd::from_slice(chunk);
-                    unsafe {
-                        Simd::gather_select_unchecked(
-                            TABLES.advanced_secrets.as_ref(),
-                            Mask::splat(true),
-                            idxes.cast(),
-                            Simd::splat(0),
-                        )
-                    }
-                    .cast()
-                })
-                .sum::<Simd<u64, SIMD_SIZE>>()
-                .reduce_sum();
-
-            sum
-        }
-    }
+    // NOTE: 8 lanes is a tiny bit faster than 4 or 16.
+    #[cfg(feature = "simd")]
+    let sum = sum_advanced_secrets_simd::<{ util::simd_lanes(8) }>(seeds, num_workers);
+
+    sum
 }
 
 fn sum_states(state_builder: Mutex<MarketStateBuilder>) -> u64 {
@@ -522,13 +516,9 @@ fn calculate_part_b_info_index(mut secret: Sequence) -> u32 {
     TABLES.start_idx[secret.0 as usize]
 }
 
-pub fn part_b(input: &str) -> u64 {
+pub fn solve_part_b(problem: &Problem) -> u64 {
     let state_builder: Mutex<MarketStateBuilder> = Mutex::new(MarketStateBuilder::new());
-
-    let secrets: Vec<_> = input
-        .lines()
-        .map(|e| Sequence(e.parse().unwrap()))
-        .collect();
+    let secrets = &problem.seeds;
 
     // NOTE: Don't split into more chunks than the number of available cores,
     // since this will just create tons of extra state that then later has to
@@ -538,7 +528,7 @@ pub fn part_b(input: &str) -> u64 {
     let mut table_indices: Vec<_> = secrets
         .par_iter()
         .with_min_len(secrets.len().div_ceil(num_workers))
-        .map(|secret| calculate_part_b_info_index(*secret))
+        .map(|secret| calculate_part_b_info_index(Sequence(*secret)))
         .collect();
 
     // Sorting this every so slightly improves runtime, because of a tiny bit
@@ -575,6 +565,14 @@ pub fn part_b(input: &str) -> u64 {
     sum_states(state_builder)
 }
 
+pub fn part_a(input: &str) -> u64 {
+    solve_part_a(&parse(input))
+}
+
+pub fn part_b(input: &str) -> u64 {
+    solve_part_b(&parse(input))
+}
+
 pub fn init() {
     // Ensure tables are constructed before test. In C++ it would be trivial to
     // build these tables at compile-time. However, Rust makes it much harder,
@@ -606,4 +604,48 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    #[cfg(feature = "simd")]
+    fn example_a_lane_count_independent() {
+        util::run_test(|| {
+            // Repeat the example's seeds, so most of them are handled by SIMD
+            // instead of ending up in the remainder.
+            let seeds: Vec<u32> = [1, 10, 100, 2024].repeat(33);
+            let expected: u64 = 33 * 37327623;
+
+            let input: String = seeds.iter().map(|e| format!("{}\n", e)).collect();
+            assert_eq!(crate::day_22::part_a(&input), expected);
+
+            let sums: [fn(&[u32], usize) -> u64; 5] = [
+                crate::day_22::sum_advanced_secrets_simd::<4>,
+                crate::day_22::sum_advanced_secrets_simd::<8>,
+                crate::day_22::sum_advanced_secrets_simd::<16>,
+                crate::day_22::sum_advanced_secrets_simd::<32>,
+                crate::day_22::sum_advanced_secrets_simd::<64>,
+            ];
+            for sum in sums {
+                assert_eq!(sum(&seeds, 2), expected);
+            }
+        });
+    }
+
+    #[test]
+    fn decode_window_round_trip() {
+        util::run_test(|| {
+            // The best sequence of changes in the example for part B.
+            let diffs: [i8; 4] = [-2, 1, -1, 3];
+
+            let mut window = crate::day_22::Window::new();
+            let mut index = 0;
+            for diff in diffs {
+                index = window.push_and_encode((diff + 9) as u32);
+            }
+            assert_eq!(crate::day_22::decode_window(index), diffs);
+
+            assert_eq!(crate::day_22::decode_window(0), [-9; 4]);
+            let max_index = crate::day_22::Window::NUM_ENCODED_INDICES as u32 - 1;
+            assert_eq!(crate::day_22::decode_window(max_index), [9; 4]);
+        });
+    }
 }
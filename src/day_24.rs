@@ -3,8 +3,8 @@ use itertools::Itertools;
 use rustc_hash::FxHashMap as HashMap;
 use std::collections::VecDeque;
 
-#[derive(Debug, PartialEq, Eq)]
-enum GateKind {
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GateKind {
     AND,
     OR,
     XOR,
@@ -22,7 +22,7 @@ impl GateKind {
 
 // NOTE: It's a bit wasteful to store the value of an input, but it makes things
 // faster when evaluating. And obviously we're optimizing for speed, not memory.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct Gate {
     kind: GateKind,
     inputs: [Option<bool>; 2],
@@ -49,14 +49,14 @@ impl Gate {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct GateInput {
     index: usize,
     port: u8,
 }
 
-#[derive(Debug)]
-struct Problem<'a> {
+#[derive(Clone, Debug)]
+pub struct Problem<'a> {
     gates: HashMap<usize, Gate>,
     connections: HashMap<usize, Vec<GateInput>>,
     output_gates: Vec<usize>,
@@ -70,7 +70,7 @@ impl<'a> TryFrom<&'a str> for Problem<'a> {
     type Error = std::string::ParseError;
 
     fn try_from(s: &'a str) -> Result<Self, Self::Error> {
-        let mut lines = s.lines();
+        let (initial_values, gates) = util::split_sections(s);
         let mut index_next: usize = 0;
 
         let mut result = Problem {
@@ -94,7 +94,7 @@ impl<'a> TryFrom<&'a str> for Problem<'a> {
         };
 
         // Extract initial states.
-        for line in lines.by_ref().take_while(|e| !e.is_empty()) {
+        for line in initial_values.lines() {
             let name = &line[..3];
             let value: bool = line[5..=5].parse::<u8>().unwrap() != 0;
             let idx = get_index(name);
@@ -102,7 +102,7 @@ impl<'a> TryFrom<&'a str> for Problem<'a> {
         }
 
         // Parse gates.
-        for line in lines.skip_while(|e| e.is_empty()) {
+        for line in gates.lines() {
             let gate_kind_end = 4 + line[4..].find(' ').unwrap();
             let gate_kind = match &line[4..gate_kind_end] {
                 "OR" => GateKind::OR,
@@ -154,6 +154,150 @@ impl<'a> TryFrom<&'a str> for Problem<'a> {
 }
 
 impl<'a> Problem<'a> {
+    /// List all gates as (output, kind, input A, input B), sorted by output
+    /// name. The inputs of each gate are sorted by name as well.
+    pub fn gates_summary(&self) -> Vec<(String, GateKind, String, String)> {
+        self.gates
+            .iter()
+            .map(|(out_idx, gate)| {
+                let [in_a, in_b] = self.gate_inputs[out_idx].map(|e| self.idx_to_name[&e]);
+                let (in_a, in_b) = (in_a.min(in_b), in_a.max(in_b));
+                (
+                    self.idx_to_name[out_idx].to_string(),
+                    gate.kind.clone(),
+                    in_a.to_string(),
+                    in_b.to_string(),
+                )
+            })
+            .sorted_unstable_by(|lhs, rhs| lhs.0.cmp(&rhs.0))
+            .collect()
+    }
+
+    /// Propagate the initial values through the circuit. Returns the value of
+    /// every wire which got one, indexed by wire.
+    fn evaluate(&self) -> HashMap<usize, bool> {
+        self.evaluate_with(&self.initial_values)
+    }
+
+    /// Same as evaluate(), but with the given (wire, value) pairs as initial
+    /// values instead of those in the input.
+    fn evaluate_with(&self, initial_values: &[(usize, bool)]) -> HashMap<usize, bool> {
+        // Gates keep track of the values on their inputs, so work on a copy.
+        let mut gates = self.gates.clone();
+        let mut wire_values: HashMap<usize, bool> = HashMap::default();
+
+        // Propagate values until there's nothing left to be done.
+        let mut values: VecDeque<(usize, bool)> = initial_values.iter().copied().collect();
+
+        // Set all initial values.
+        while let Some((out_idx, value)) = values.pop_front() {
+            wire_values.insert(out_idx, value);
+
+            match self.connections.get(&out_idx) {
+                None => (),
+                Some(conns) => {
+                    for conn in conns {
+                        let gate = gates.get_mut(&(conn.index as usize)).unwrap();
+                        gate.set_input(conn.port as usize, value);
+
+                        if let Some(gate_value) = gate.evaluate() {
+                            values.push_back((conn.index as usize, gate_value));
+                        }
+                    }
+                }
+            }
+        }
+
+        wire_values
+    }
+
+    /// Evaluate the circuit and return the number formed by the z?? wires,
+    /// with z00 being the least significant bit.
+    pub fn output_value(&self) -> u64 {
+        self.output_value_of(&self.evaluate()).unwrap()
+    }
+
+    /// Number formed by the z?? wires, or None if not all of them got a value,
+    /// e.g. because swapped outputs formed a loop.
+    fn output_value_of(&self, wire_values: &HashMap<usize, bool>) -> Option<u64> {
+        self.output_gates
+            .iter()
+            .enumerate()
+            .map(|(output_pos, gate_idx)| Some((*wire_values.get(gate_idx)? as u64) << output_pos))
+            .sum()
+    }
+
+    /// Evaluate the circuit and return the value of the wire with the given
+    /// name. Returns None if there's no such wire, or it never gets a value.
+    pub fn wire_value(&self, name: &str) -> Option<bool> {
+        let idx = self.name_to_idx.get(name)?;
+        self.evaluate().get(idx).copied()
+    }
+
+    /// Swap the outputs of the gates driving each given pair of wires, then
+    /// check that the circuit adds the x?? and y?? inputs for every sample.
+    /// Operands are truncated to the number of input bits. Returns false if
+    /// any of the wires isn't the output of a gate, or is swapped with itself.
+    pub fn is_correct_adder_after_swaps(
+        &self,
+        swaps: &[(&str, &str)],
+        samples: &[(u64, u64)],
+    ) -> bool {
+        let mut problem = self.clone();
+        for (name_a, name_b) in swaps {
+            let (Some(&idx_a), Some(&idx_b)) =
+                (self.name_to_idx.get(name_a), self.name_to_idx.get(name_b))
+            else {
+                return false;
+            };
+            if idx_a == idx_b
+                || !self.gates.contains_key(&idx_a)
+                || !self.gates.contains_key(&idx_b)
+            {
+                return false;
+            }
+            problem.swap_outputs(idx_a, idx_b);
+        }
+
+        let num_input_bits = self.output_gates.len() - 1;
+        let mask = (1u64 << num_input_bits) - 1;
+        samples.iter().all(|&(x, y)| {
+            let (x, y) = (x & mask, y & mask);
+            let initial_values: Vec<(usize, bool)> = self
+                .initial_values
+                .iter()
+                .map(|&(idx, _)| {
+                    let name = self.idx_to_name[&idx];
+                    let operand = if name.starts_with('x') { x } else { y };
+                    let bit: usize = name[1..].parse().unwrap();
+                    (idx, (operand >> bit) & 1 != 0)
+                })
+                .collect();
+
+            problem.output_value_of(&problem.evaluate_with(&initial_values)) == Some(x + y)
+        })
+    }
+
+    /// Swap the outputs of two gates, i.e. each of them now drives the wire
+    /// the other one used to drive.
+    fn swap_outputs(&mut self, idx_a: usize, idx_b: usize) {
+        let [gate_a, gate_b] = [idx_a, idx_b].map(|e| self.gates.remove(&e).unwrap());
+        self.gates.insert(idx_a, gate_b);
+        self.gates.insert(idx_b, gate_a);
+
+        let [inputs_a, inputs_b] = [idx_a, idx_b].map(|e| self.gate_inputs.remove(&e).unwrap());
+        self.gate_inputs.insert(idx_a, inputs_b);
+        self.gate_inputs.insert(idx_b, inputs_a);
+
+        for gate_input in self.connections.values_mut().flatten() {
+            if gate_input.index == idx_a {
+                gate_input.index = idx_b;
+            } else if gate_input.index == idx_b {
+                gate_input.index = idx_a;
+            }
+        }
+    }
+
     // NOTE: These functions are only implemented as far as was necessary to
     // solve the given input. They might not work on someone else's input.
 
@@ -251,46 +395,26 @@ impl<'a> Problem<'a> {
     }
 }
 
-pub fn part_a(input: &str) -> u64 {
-    let mut problem = Problem::try_from(input).unwrap();
+/// Parse the input, such that it can be solved separately afterwards.
+pub fn parse(input: &str) -> Problem<'_> {
+    let problem = Problem::try_from(input).unwrap();
     log::trace!("{:#?}", problem);
-
-    // Propagate values until there's nothing left to be done.
-    let mut values: VecDeque<(usize, bool)> = problem.initial_values.iter().copied().collect();
-
-    // Set all initial values.
-    while !values.is_empty() {
-        let (out_idx, value) = values.pop_front().unwrap();
-
-        match problem.connections.get(&out_idx) {
-            None => (),
-            Some(conns) => {
-                for conn in conns {
-                    let gate = problem.gates.get_mut(&(conn.index as usize)).unwrap();
-                    gate.set_input(conn.port as usize, value);
-
-                    if let Some(gate_value) = gate.evaluate() {
-                        values.push_back((conn.index as usize, gate_value));
-                    }
-                }
-            }
-        }
-    }
-
     problem
-        .output_gates
-        .iter()
-        .enumerate()
-        .map(|(output_pos, gate_idx)| {
-            (problem.gates[gate_idx].evaluate().unwrap() as u64) << output_pos
-        })
-        .sum()
 }
 
-pub fn part_b(input: &str) -> String {
-    const NUM_SWAPPED_WIRES: usize = 4 * 2;
+pub fn solve_part_a(problem: &Problem) -> u64 {
+    problem.output_value()
+}
 
-    let problem = Problem::try_from(input).unwrap();
+pub fn solve_part_b(problem: &Problem) -> String {
+    find_swapped_wires(problem, 4)
+}
+
+/// Find the wires of the given number of pairs of swapped gate outputs, which
+/// keep the circuit from being a ripple-carry adder. Returns the sorted names
+/// of those wires, separated by commas.
+pub fn find_swapped_wires(problem: &Problem, num_swapped_pairs: usize) -> String {
+    let num_swapped_wires = 2 * num_swapped_pairs;
 
     // Check that the gates represent a ripple-carry adder. This requires a full
     // adder (5 gates), except for the first bit, which requires only a half
@@ -329,16 +453,24 @@ pub fn part_b(input: &str) -> String {
             problem.name_to_idx[format!("z{:02}", in_idx).as_str()],
         );
 
-        if wrong_conns.len() >= NUM_SWAPPED_WIRES {
+        if wrong_conns.len() >= num_swapped_wires {
             break;
         }
     }
-    assert_eq!(wrong_conns.len(), NUM_SWAPPED_WIRES);
+    assert_eq!(wrong_conns.len(), num_swapped_wires);
 
     wrong_conns.sort_unstable_by_key(|e| problem.idx_to_name[e]);
     wrong_conns.iter().map(|e| problem.idx_to_name[e]).join(",")
 }
 
+pub fn part_a(input: &str) -> u64 {
+    solve_part_a(&parse(input))
+}
+
+pub fn part_b(input: &str) -> String {
+    solve_part_b(&parse(input))
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -363,6 +495,103 @@ mod tests {
         });
     }
 
+    #[test]
+    fn wire_values() {
+        util::run_test(|| {
+            let input = util::read_resource("example_24-part_2.txt").unwrap();
+            let problem = crate::day_24::parse(&input);
+            assert_eq!(problem.output_value(), crate::day_24::part_a(&input));
+
+            // Initial values, intermediate wires and output bits.
+            assert_eq!(problem.wire_value("x00"), Some(true));
+            assert_eq!(problem.wire_value("bfw"), Some(true));
+            assert_eq!(problem.wire_value("ffh"), Some(false));
+            assert_eq!(problem.wire_value("kjc"), Some(false));
+            assert_eq!(problem.wire_value("mjb"), Some(true));
+            assert_eq!(problem.wire_value("z00"), Some(false));
+            assert_eq!(problem.wire_value("z03"), Some(true));
+            assert_eq!(problem.wire_value("abc"), None);
+        });
+    }
+
+    #[test]
+    fn gates_summary() {
+        use crate::day_24::GateKind;
+
+        util::run_test(|| {
+            let input = "x00: 1\nx01: 1\nx02: 1\ny00: 0\ny01: 1\ny02: 0\n\n\
+                         x00 AND y00 -> z00\nx01 XOR y01 -> z01\ny02 OR x02 -> z02\n";
+            let to_entry = |out: &str, kind, in_a: &str, in_b: &str| {
+                (out.to_string(), kind, in_a.to_string(), in_b.to_string())
+            };
+
+            assert_eq!(
+                crate::day_24::parse(input).gates_summary(),
+                [
+                    to_entry("z00", GateKind::AND, "x00", "y00"),
+                    to_entry("z01", GateKind::XOR, "x01", "y01"),
+                    to_entry("z02", GateKind::OR, "x02", "y02"),
+                ]
+            );
+        });
+    }
+
+    // Six bit ripple-carry adder, with a sum output swapped with a carry AND in
+    // bit 2, the input XOR and AND swapped in bit 3, and a sum output swapped
+    // with the carry out in bit 4.
+    const SWAPPED_ADDER: &str = "x00: 0\nx01: 1\nx02: 0\nx03: 1\nx04: 0\nx05: 1\n\
+                                 y00: 0\ny01: 1\ny02: 1\ny03: 0\ny04: 1\ny05: 1\n\n\
+                                 x00 XOR y00 -> z00\nx00 AND y00 -> w01\n\
+                                 x01 XOR y01 -> w02\nx01 AND y01 -> w03\nw02 XOR w01 -> z01\n\
+                                 w02 AND w01 -> w04\nw03 OR w04 -> w05\n\
+                                 x02 XOR y02 -> w06\nx02 AND y02 -> w07\nw06 XOR w05 -> w08\n\
+                                 w06 AND w05 -> z02\nw07 OR w08 -> w09\n\
+                                 x03 XOR y03 -> w11\nx03 AND y03 -> w10\nw10 XOR w09 -> z03\n\
+                                 w10 AND w09 -> w12\nw11 OR w12 -> w13\n\
+                                 x04 XOR y04 -> w14\nx04 AND y04 -> w15\nw14 XOR w13 -> w17\n\
+                                 w14 AND w13 -> w16\nw15 OR w16 -> z04\n\
+                                 x05 XOR y05 -> w18\nx05 AND y05 -> w19\nw18 XOR w17 -> z05\n\
+                                 w18 AND w17 -> w20\nw19 OR w20 -> z06\n";
+
+    #[test]
+    fn three_swapped_pairs() {
+        util::run_test(|| {
+            let problem = crate::day_24::parse(SWAPPED_ADDER);
+            assert_eq!(
+                crate::day_24::find_swapped_wires(&problem, 3),
+                "w08,w10,w11,w17,z02,z04"
+            );
+        });
+    }
+
+    #[test]
+    fn verify_swaps() {
+        util::run_test(|| {
+            let problem = crate::day_24::parse(SWAPPED_ADDER);
+            let samples = [(0, 0), (1, 1), (21, 42), (63, 63), (37, 11), (4, 60)];
+
+            let swaps = [("z02", "w08"), ("w10", "w11"), ("z04", "w17")];
+            assert!(problem.is_correct_adder_after_swaps(&swaps, &samples));
+
+            // Each swap on its own isn't enough.
+            assert!(!problem.is_correct_adder_after_swaps(&[], &samples));
+            for swap in swaps {
+                assert!(!problem.is_correct_adder_after_swaps(&[swap], &samples));
+            }
+
+            // Swapping a carry with a wire it depends on creates a loop.
+            let swaps = [("z02", "w08"), ("w10", "w11"), ("w09", "w12")];
+            assert!(!problem.is_correct_adder_after_swaps(&swaps, &samples));
+
+            // Inputs can't be swapped, since no gate drives them.
+            let swaps = [("z02", "w08"), ("w10", "w11"), ("z04", "x00")];
+            assert!(!problem.is_correct_adder_after_swaps(&swaps, &samples));
+
+            // Nor can a wire be swapped with itself.
+            assert!(!problem.is_correct_adder_after_swaps(&[("z02", "z02")], &samples));
+        });
+    }
+
     // Part B is written explicitly to check a carry-chain adder, so won't work
     // for the example.
 }
@@ -1,10 +1,17 @@
 use rayon::prelude::*;
+#[cfg(feature = "simd")]
 use std::simd::{
     cmp::{SimdPartialEq, SimdPartialOrd},
-    Simd,
+    Select, Simd,
 };
 
-type Heights = Simd<u8, 8>;
+// NOTE: Only the first Problem::NUM_ELEM lanes are used, the rest stays 0.
+#[cfg(feature = "simd")]
+const HEIGHTS_LANES: usize = util::simd_lanes(8);
+#[cfg(feature = "simd")]
+type Heights = Simd<u8, HEIGHTS_LANES>;
+#[cfg(not(feature = "simd"))]
+type Heights = [u8; 8];
 
 #[derive(Debug)]
 struct Problem {
@@ -22,24 +29,9 @@ impl TryFrom<&str> for Problem {
         };
 
         const LINES_PER_ENTRY: usize = Problem::MAX_HEIGHT as usize + 2;
-        for chunk in s
-            .lines()
-            .into_iter()
-            .filter(|line| !line.is_empty())
-            .array_chunks::<LINES_PER_ENTRY>()
-        {
-            let heights: Heights = chunk[1..LINES_PER_ENTRY - 1]
-                .iter()
-                .map(|line| -> Heights {
-                    assert_eq!(line.len(), Problem::NUM_ELEM as usize);
-                    let result = Simd::load_or_default(line.as_bytes());
-                    let mask = result.simd_eq(Simd::splat(b'#'));
-                    mask.select(Simd::splat(1u8), Simd::splat(0u8))
-                })
-                .fold(Heights::default(), |mut acc, iter| {
-                    acc += iter;
-                    acc
-                });
+        let lines: Vec<&str> = s.lines().filter(|line| !line.is_empty()).collect();
+        for chunk in lines.chunks_exact(LINES_PER_ENTRY) {
+            let heights = Problem::parse_heights(&chunk[1..LINES_PER_ENTRY - 1]);
 
             match chunk[0].as_bytes()[0] {
                 b'#' => result.locks.push(heights),
@@ -56,6 +48,40 @@ impl Problem {
     const NUM_ELEM: u8 = 5;
     const MAX_HEIGHT: u8 = 5;
 
+    #[cfg(feature = "simd")]
+    fn parse_heights(lines: &[&str]) -> Heights {
+        lines
+            .iter()
+            .map(|line| -> Heights {
+                assert_eq!(line.len(), Problem::NUM_ELEM as usize);
+                let result = Simd::load_or_default(line.as_bytes());
+                let mask = result.simd_eq(Simd::splat(b'#'));
+                mask.select(Simd::splat(1u8), Simd::splat(0u8))
+            })
+            .fold(Heights::default(), |mut acc, iter| {
+                acc += iter;
+                acc
+            })
+    }
+
+    #[cfg(not(feature = "simd"))]
+    fn parse_heights(lines: &[&str]) -> Heights {
+        let mut heights = Heights::default();
+        for line in lines {
+            assert_eq!(line.len(), Problem::NUM_ELEM as usize);
+            for (height, value) in heights.iter_mut().zip(line.bytes()) {
+                *height += (value == b'#') as u8;
+            }
+        }
+        heights
+    }
+
+    /// Per-column heights, i.e. without the unused lanes.
+    fn decode_heights(heights: &Heights) -> [u8; Problem::NUM_ELEM as usize] {
+        std::array::from_fn(|idx| heights[idx])
+    }
+
+    #[cfg(feature = "simd")]
     fn overlap(lsh: &Heights, rhs: &Heights) -> bool {
         // NOTE: Storing the sum of elements and short-circuiting the element-
         // wise comparison if the sum of elements > NUM_ELEM * MAX_HEIGHT
@@ -64,6 +90,13 @@ impl Problem {
         // 16 elements up to 3 rhs can be compared at the same time.
         (lsh + rhs).simd_gt(Simd::splat(Self::MAX_HEIGHT)).any()
     }
+
+    #[cfg(not(feature = "simd"))]
+    fn overlap(lsh: &Heights, rhs: &Heights) -> bool {
+        lsh.iter()
+            .zip(rhs.iter())
+            .any(|(l, r)| l + r > Self::MAX_HEIGHT)
+    }
 }
 
 pub fn part_a(input: &str) -> u64 {
@@ -83,6 +116,18 @@ pub fn part_a(input: &str) -> u64 {
         .sum()
 }
 
+/// Per-column heights of all locks, in the order they appear in the input.
+pub fn lock_heights(input: &str) -> Vec<[u8; 5]> {
+    let problem = Problem::try_from(input).unwrap();
+    problem.locks.iter().map(Problem::decode_heights).collect()
+}
+
+/// Per-column heights of all keys, in the order they appear in the input.
+pub fn key_heights(input: &str) -> Vec<[u8; 5]> {
+    let problem = Problem::try_from(input).unwrap();
+    problem.keys.iter().map(Problem::decode_heights).collect()
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -96,5 +141,20 @@ mod tests {
         });
     }
 
+    #[test]
+    fn example_heights() {
+        util::run_test(|| {
+            let input = util::read_resource("example_25.txt").unwrap();
+
+            let locks = crate::day_25::lock_heights(&input);
+            assert_eq!(locks.len(), 2);
+            assert_eq!(locks[0], [0, 5, 3, 4, 3]);
+
+            let keys = crate::day_25::key_heights(&input);
+            assert_eq!(keys.len(), 3);
+            assert_eq!(keys[0], [5, 0, 2, 1, 3]);
+        });
+    }
+
     // No part B on the last problem.
 }
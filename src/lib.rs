@@ -1,8 +1,4 @@
-#![feature(array_chunks)]
-#![feature(duration_millis_float)]
-#![feature(int_roundings)]
-#![feature(iter_array_chunks)]
-#![feature(portable_simd)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 
 pub mod day_01;
 pub mod day_02;
@@ -29,3 +25,163 @@ pub mod day_22;
 pub mod day_23;
 pub mod day_24;
 pub mod day_25;
+
+/// All solvers as (day, part, solver) tuples, ordered by day and part.
+macro_rules! solvers {
+    ($($day: ident: [$($part: ident),+]),+ $(,)?) => {
+        const SOLVERS: &[(&str, &str, fn(&str) -> String)] = &[
+            $($(
+                (stringify!($day), stringify!($part), |input| $day::$part(input).to_string()),
+            )+)+
+        ];
+    };
+}
+
+solvers!(
+    day_01: [part_a, part_b],
+    day_02: [part_a, part_b],
+    day_03: [part_a, part_b],
+    day_04: [part_a, part_b],
+    day_05: [part_a, part_b],
+    day_06: [part_a, part_b],
+    day_07: [part_a, part_b],
+    day_08: [part_a, part_b],
+    day_09: [part_a, part_b],
+    day_10: [part_a, part_b],
+    day_11: [part_a, part_b],
+    day_12: [part_a, part_b],
+    day_13: [part_a, part_b],
+    day_14: [part_a, part_b],
+    day_15: [part_a, part_b],
+    day_16: [part_a, part_b],
+    day_17: [part_a, part_b],
+    day_18: [part_a, part_b],
+    day_19: [part_a, part_b],
+    day_20: [part_a, part_b],
+    day_21: [part_a, part_b],
+    day_22: [part_a, part_b],
+    day_23: [part_a, part_b],
+    day_24: [part_a, part_b],
+    day_25: [part_a],
+);
+
+/// Runs the solver for the given day and part, e.g. "day_05" and "part_a", on
+/// the given input. Returns None if there's no such solver.
+pub fn solve(day: &str, part: &str, input: &str) -> Option<String> {
+    SOLVERS
+        .iter()
+        .find(|&&(solver_day, solver_part, _)| (solver_day, solver_part) == (day, part))
+        .map(|(_, _, solver)| solver(input))
+}
+
+/// Runs every part of every day once on its input, i.e. on resources/<day>.txt.
+/// Returns the name (e.g. "Day 05 - Part A"), solution and duration of each of
+/// them, in order. Parts for which there's no input are still included, but
+/// with an empty solution and a zero duration.
+pub fn run_all() -> Vec<(String, String, std::time::Duration)> {
+    SOLVERS
+        .iter()
+        .map(|&(day, part, solver)| {
+            let name = format!("{} - {}", heck::AsTitleCase(day), heck::AsTitleCase(part));
+
+            match util::read_resource(&format!("{}.txt", day)) {
+                Ok(input) => {
+                    let start = std::time::Instant::now();
+                    let solution = solver(&input);
+                    (name, solution, start.elapsed())
+                }
+                Err(_) => (name, String::new(), std::time::Duration::ZERO),
+            }
+        })
+        .collect()
+}
+
+/// Only runs the parser for the given day on the given input, e.g. to fuzz it.
+/// Returns None if there's no separate parser for that day.
+pub fn validate(day: &str, input: &str) -> Option<Result<(), util::InputError>> {
+    macro_rules! dispatch {
+        ($($day: ident),+ $(,)?) => {
+            match day {
+                $(stringify!($day) => Some($day::parse(input)),)+
+                _ => None,
+            }
+        };
+    }
+
+    dispatch!(day_06, day_15, day_16, day_19)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn solve_dispatch() {
+        util::run_test(|| {
+            let input = util::read_resource("example_05.txt").unwrap();
+            assert_eq!(
+                crate::solve("day_05", "part_a", &input),
+                Some(crate::day_05::part_a(&input).to_string())
+            );
+        });
+    }
+
+    #[test]
+    fn solve_dispatch_unknown() {
+        util::run_test(|| {
+            assert_eq!(crate::solve("day_26", "part_a", ""), None);
+            assert_eq!(crate::solve("day_25", "part_b", ""), None);
+        });
+    }
+
+    #[test]
+    fn run_all() {
+        util::run_test(|| {
+            let results = crate::run_all();
+            assert_eq!(results.len(), 49);
+            assert_eq!(results[0].0, "Day 01 - Part A");
+            assert_eq!(results[48].0, "Day 25 - Part A");
+
+            for (idx, (name, solution, _)) in results.iter().enumerate() {
+                let day = format!("day_{:02}", idx / 2 + 1);
+                if util::read_resource(&format!("{}.txt", day)).is_ok() {
+                    assert!(!solution.is_empty(), "{} has no solution", name);
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn validate_truncated_grid() {
+        util::run_test(|| {
+            let maze = "#####\n#S..#\n#..E#\n#####\n";
+            assert_eq!(crate::validate("day_16", maze), Some(Ok(())));
+
+            // Input cut off halfway through the last row.
+            let truncated = &maze[..maze.len() - 3];
+            assert_eq!(
+                crate::validate("day_16", truncated),
+                Some(Err(util::InputError::RaggedGrid {
+                    row: 3,
+                    expected: 5,
+                    actual: 3
+                }))
+            );
+
+            let lab = "....#\n.....\n..^..\n";
+            assert_eq!(crate::validate("day_06", lab), Some(Ok(())));
+            assert!(matches!(
+                crate::validate("day_06", &lab[..lab.len() - 2]),
+                Some(Err(util::InputError::RaggedGrid { row: 2, .. }))
+            ));
+
+            // Truncating the warehouse map means there are no moves either.
+            let warehouse = "#####\n#.@O#\n#####\n\n<>^v\n";
+            assert_eq!(crate::validate("day_15", warehouse), Some(Ok(())));
+            assert!(matches!(
+                crate::validate("day_15", &warehouse[..10]),
+                Some(Err(util::InputError::RaggedGrid { row: 1, .. }))
+            ));
+
+            assert_eq!(crate::validate("day_01", ""), None);
+        });
+    }
+}
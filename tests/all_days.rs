@@ -0,0 +1,47 @@
+//! Runs every day on its real input and checks the answers against the ones
+//! stored in resources/expected.txt. Each line of that file has the form
+//! "<day> <part> <answer>", e.g. "day_05 part_a 143". Inputs are read from
+//! resources/<day>.txt, same as the binaries do. Since neither the inputs nor
+//! the answers can be shared, anything missing is skipped.
+
+#[test]
+fn all_days() {
+    util::run_test(|| {
+        let Ok(expected) = util::read_resource("expected.txt") else {
+            eprintln!("Skipping: no expected answers found");
+            return;
+        };
+
+        let mut num_checked = 0;
+        for line in expected.lines().filter(|e| !e.trim().is_empty()) {
+            let mut fields = line.splitn(3, ' ');
+            let (Some(day), Some(part), Some(answer)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                panic!("malformed line in expected.txt: {:?}", line);
+            };
+
+            let Ok(input) = util::read_resource(&format!("{}.txt", day)) else {
+                eprintln!("Skipping {} {}: no input found", day, part);
+                continue;
+            };
+
+            // Days with a separate parser must accept their real input.
+            if let Some(result) = aoc_2024::validate(day, &input) {
+                assert_eq!(result, Ok(()), "{} rejects its input", day);
+            }
+
+            let solution = aoc_2024::solve(day, part, &input);
+            assert_eq!(
+                solution.as_deref(),
+                Some(answer.trim()),
+                "wrong answer for {} {}",
+                day,
+                part
+            );
+            num_checked += 1;
+        }
+
+        eprintln!("Checked {} answers", num_checked);
+    });
+}
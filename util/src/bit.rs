@@ -12,3 +12,81 @@ pub fn digit_width_base10(x: u64) -> u32 {
         value => (unsafe { NonZero::new_unchecked(value).ilog10() }) + 1,
     }
 }
+
+/// Returns offset + (offset + 1) + ... + (offset + length - 1), i.e. the sum
+/// of the indices of a block of the given length starting at the given offset.
+/// Returns 0 if the length is 0.
+pub fn arithmetic_block_sum(offset: usize, length: usize) -> usize {
+    length * offset + (length * length.saturating_sub(1)) / 2
+}
+
+/// Extended Euclidean algorithm. Returns (gcd, x, y) such that
+/// a * x + b * y == gcd.
+pub fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
+    match b {
+        0 => (a, 1, 0),
+        _ => {
+            let (gcd, x, y) = egcd(b, a.rem_euclid(b));
+            (gcd, y, x - a.div_euclid(b) * y)
+        }
+    }
+}
+
+/// Returns x such that a * x == 1 (mod m), with 0 <= x < m, or None if a and
+/// m aren't coprime or m isn't strictly positive.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    if m <= 0 {
+        return None;
+    }
+
+    match egcd(a.rem_euclid(m), m) {
+        (1, x, _) => Some(x.rem_euclid(m)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn egcd_identity() {
+        for (a, b) in [(240, 46), (46, 240), (17, 5), (12, 0), (0, 7)] {
+            let (gcd, x, y) = egcd(a, b);
+            assert_eq!(a * x + b * y, gcd);
+        }
+        assert_eq!(egcd(240, 46).0, 2);
+        assert_eq!(egcd(17, 5).0, 1);
+    }
+
+    #[test]
+    fn mod_inverse_exists() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(101, 103), Some(51));
+    }
+
+    #[test]
+    fn mod_inverse_missing() {
+        assert_eq!(mod_inverse(4, 10), None);
+        assert_eq!(mod_inverse(0, 7), None);
+        assert_eq!(mod_inverse(3, 0), None);
+        assert_eq!(mod_inverse(3, -7), None);
+    }
+
+    #[test]
+    fn arithmetic_block_sum_empty() {
+        assert_eq!(arithmetic_block_sum(0, 0), 0);
+        assert_eq!(arithmetic_block_sum(17, 0), 0);
+    }
+
+    #[test]
+    fn arithmetic_block_sum_matches_naive_sum() {
+        for offset in 0..10 {
+            for length in 0..10 {
+                let expected: usize = (offset..offset + length).sum();
+                assert_eq!(arithmetic_block_sum(offset, length), expected);
+            }
+        }
+    }
+}
@@ -23,6 +23,20 @@ impl Coord {
         self.into()
     }
 
+    /// Same as as_pair(), but keeps the sign, for coordinates which may
+    /// legitimately be negative, e.g. offsets or velocities.
+    pub fn as_signed_pair(&self) -> (isize, isize) {
+        (self.row, self.col)
+    }
+
+    /// Counterpart of as_signed_pair(), for values parsed as signed integers.
+    pub fn from_signed(row: i64, col: i64) -> Coord {
+        Coord {
+            row: row as isize,
+            col: col as isize,
+        }
+    }
+
     pub fn has_negatives(&self) -> bool {
         (self.row < 0) || (self.col < 0)
     }
@@ -44,6 +58,63 @@ impl Coord {
     pub fn manhattan_distance(&self, other: &Coord) -> usize {
         self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
     }
+
+    /// Iterates over all coordinates within the given Manhattan distance of
+    /// this one (including itself), in row-major order. Each coordinate is
+    /// paired with its distance to this one.
+    pub fn within_manhattan(&self, radius: u16) -> impl Iterator<Item = (Coord, u16)> {
+        let center = *self;
+        let radius = radius as isize;
+
+        (-radius..=radius).flat_map(move |row_offset| {
+            let col_radius = radius - row_offset.abs();
+            (-col_radius..=col_radius).map(move |col_offset| {
+                let distance = (row_offset.abs() + col_offset.abs()) as u16;
+                (
+                    Coord {
+                        row: center.row + row_offset,
+                        col: center.col + col_offset,
+                    },
+                    distance,
+                )
+            })
+        })
+    }
+
+    /// Same as Add, but returns None instead of overflowing.
+    pub fn checked_add(&self, other: Coord) -> Option<Coord> {
+        Some(Coord {
+            row: self.row.checked_add(other.row)?,
+            col: self.col.checked_add(other.col)?,
+        })
+    }
+
+    /// Same as Sub, but returns None instead of overflowing.
+    pub fn checked_sub(&self, other: Coord) -> Option<Coord> {
+        Some(Coord {
+            row: self.row.checked_sub(other.row)?,
+            col: self.col.checked_sub(other.col)?,
+        })
+    }
+
+    /// Same as Mul, but returns None instead of overflowing.
+    pub fn checked_mul(&self, factor: isize) -> Option<Coord> {
+        Some(Coord {
+            row: self.row.checked_mul(factor)?,
+            col: self.col.checked_mul(factor)?,
+        })
+    }
+
+    /// Same as From<(usize, usize)>, but returns an error instead of panicking
+    /// if either value doesn't fit in an isize. (Implementing TryFrom isn't
+    /// possible, since it would conflict with the blanket implementation
+    /// provided for From.)
+    pub fn try_from_pair((row, col): (usize, usize)) -> Result<Coord, std::num::TryFromIntError> {
+        Ok(Coord {
+            row: row.try_into()?,
+            col: col.try_into()?,
+        })
+    }
 }
 
 impl std::ops::Add for Coord {
@@ -103,6 +174,24 @@ impl std::ops::Add<Direction> for &Coord {
     }
 }
 
+// By-reference versions, such that iterating over e.g. a slice of directions
+// doesn't require dereferencing each one.
+impl std::ops::Add<&Direction> for Coord {
+    type Output = Self;
+
+    fn add(self, other: &Direction) -> Self {
+        self + *other
+    }
+}
+
+impl std::ops::Add<&Direction> for &Coord {
+    type Output = Coord;
+
+    fn add(self, other: &Direction) -> Coord {
+        self + *other
+    }
+}
+
 impl std::ops::AddAssign for Coord {
     fn add_assign(&mut self, rhs: Coord) {
         self.row += rhs.row;
@@ -187,6 +276,18 @@ impl Direction {
             Direction::SouthWest => Coord { row: 1, col: -1 },
         }
     }
+
+    /// Returns the direction an arrow glyph (i.e. `^`, `>`, `v` or `<`)
+    /// points in, or None if the given character isn't an arrow.
+    pub const fn from_ascii(ascii: u8) -> Option<Direction> {
+        match ascii {
+            b'^' => Some(Direction::North),
+            b'>' => Some(Direction::East),
+            b'v' => Some(Direction::South),
+            b'<' => Some(Direction::West),
+            _ => None,
+        }
+    }
 }
 
 impl From<Direction> for Coord {
@@ -374,6 +475,11 @@ impl Iterator for DirectedCoordRangeIterator {
     }
 
     fn last(self) -> Option<Self::Item> {
+        // Nothing left to iterate over, e.g. because the range is empty.
+        if self.offset >= self.range.len {
+            return None;
+        }
+
         let dir_offset: Coord = self.range.dir.into();
         let max_steps = isize::try_from(self.range.len).unwrap() - 1;
         Some(self.range.start + max_steps * dir_offset)
@@ -385,3 +491,313 @@ impl ExactSizeIterator for DirectedCoordRangeIterator {
         self.range.len - self.offset
     }
 }
+
+/// Returns the smallest and largest of the given coordinates, using Coord's
+/// ordering (i.e. row-major). Contrary to bounding_box(), both returned
+/// coordinates are always one of the given ones. Returns None if there are no
+/// coordinates.
+pub fn coord_minmax<T>(coords: T) -> Option<(Coord, Coord)>
+where
+    T: Iterator<Item = Coord>,
+{
+    coords.fold(None, |acc, coord| match acc {
+        None => Some((coord, coord)),
+        Some((min, max)) => Some((min.min(coord), max.max(coord))),
+    })
+}
+
+/// Returns the top-left and bottom-right corners of the smallest rectangle
+/// containing all given coordinates, or None if there are no coordinates.
+pub fn bounding_box<T>(coords: T) -> Option<(Coord, Coord)>
+where
+    T: Iterator<Item = Coord>,
+{
+    coords.fold(None, |acc, coord| match acc {
+        None => Some((coord, coord)),
+        Some((min, max)) => Some((
+            Coord {
+                row: min.row.min(coord.row),
+                col: min.col.min(coord.col),
+            },
+            Coord {
+                row: max.row.max(coord.row),
+                col: max.col.max(coord.col),
+            },
+        )),
+    })
+}
+
+/// Iterates over all coordinates in the rectangle spanned by the given corners
+/// (both inclusive), in row-major order.
+pub fn iter_rect(min: Coord, max: Coord) -> impl Iterator<Item = Coord> {
+    (min.row..=max.row).flat_map(move |row| (min.col..=max.col).map(move |col| Coord { row, col }))
+}
+
+/// Iterates over the integer coordinates along the segment from one coordinate
+/// to another (both inclusive), using Bresenham's line algorithm. For
+/// horizontal, vertical and diagonal segments, these are exactly the points on
+/// the segment.
+pub fn line(from: Coord, to: Coord) -> impl Iterator<Item = Coord> {
+    let delta_col = (to.col - from.col).abs();
+    let delta_row = -(to.row - from.row).abs();
+    let step = Coord {
+        row: (to.row - from.row).signum(),
+        col: (to.col - from.col).signum(),
+    };
+
+    // Every step moves at least one square along the longest axis.
+    let num_points = delta_col.max(-delta_row) + 1;
+    let mut pos = from;
+    let mut error = delta_col + delta_row;
+
+    (0..num_points).map(move |_| {
+        let result = pos;
+        let double_error = 2 * error;
+        if double_error >= delta_row {
+            error += delta_row;
+            pos.col += step.col;
+        }
+        if double_error <= delta_col {
+            error += delta_col;
+            pos.row += step.row;
+        }
+        result
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_arithmetic() {
+        let coord = Coord { row: 3, col: -2 };
+        let other = Coord { row: 1, col: 1 };
+        assert_eq!(coord.checked_add(other), Some(coord + other));
+        assert_eq!(coord.checked_sub(other), Some(coord - other));
+        assert_eq!(coord.checked_mul(-4), Some(-4_isize * coord));
+    }
+
+    #[test]
+    fn signed_pair_round_trip() {
+        for (row, col) in [(0, 0), (-3, 7), (4, -1), (-20, -20)] {
+            let coord = Coord::from_signed(row, col);
+            assert_eq!(
+                coord,
+                Coord {
+                    row: row as isize,
+                    col: col as isize
+                }
+            );
+            assert_eq!(coord.as_signed_pair(), (row as isize, col as isize));
+        }
+
+        let offset = Coord { row: 2, col: 5 } - Coord { row: 6, col: 1 };
+        assert_eq!(offset.as_signed_pair(), (-4, 4));
+    }
+
+    #[test]
+    fn add_direction() {
+        use strum::IntoEnumIterator;
+
+        let coord = Coord { row: 3, col: -2 };
+        for dir in Direction::iter() {
+            let expected = coord + dir.to_coord();
+            assert_eq!(coord + dir, expected);
+            assert_eq!(&coord + dir, expected);
+            assert_eq!(coord + &dir, expected);
+            assert_eq!(&coord + &dir, expected);
+        }
+
+        let neighbours: Vec<_> = [Direction::North, Direction::SouthEast]
+            .iter()
+            .map(|dir| &coord + dir)
+            .collect();
+        assert_eq!(
+            neighbours,
+            [Coord { row: 2, col: -2 }, Coord { row: 4, col: -1 }]
+        );
+    }
+
+    #[test]
+    fn directed_range_last() {
+        use strum::IntoEnumIterator;
+
+        let start = Coord { row: 3, col: -2 };
+        for dir in Direction::iter() {
+            for len in 0..5 {
+                let range = DirectedCoordRange { start, len, dir };
+                assert_eq!(range.iter().last(), range.iter().fold(None, |_, e| Some(e)));
+                assert_eq!(range.iter().count(), len);
+            }
+
+            // Partially and fully consumed iterators.
+            let mut iter = DirectedCoordRange { start, len: 2, dir }.iter();
+            iter.next();
+            assert_eq!(iter.last(), Some(start + dir));
+            iter.next();
+            iter.next();
+            assert_eq!(iter.last(), None);
+        }
+
+        let range = DirectedCoordRange {
+            start,
+            len: 3,
+            dir: Direction::SouthWest,
+        };
+        assert_eq!(range.iter().last(), Some(Coord { row: 5, col: -4 }));
+        let empty = DirectedCoordRange { len: 0, ..range };
+        assert_eq!(empty.iter().last(), None);
+    }
+
+    #[test]
+    fn checked_arithmetic_overflow() {
+        let max = Coord {
+            row: isize::MAX,
+            col: 0,
+        };
+        let min = Coord {
+            row: 0,
+            col: isize::MIN,
+        };
+        let one = Coord { row: 1, col: 1 };
+        assert_eq!(max.checked_add(one), None);
+        assert_eq!(min.checked_sub(one), None);
+        assert_eq!(max.checked_mul(2), None);
+        assert_eq!(min.checked_mul(-1), None);
+    }
+
+    #[test]
+    fn try_from_pair() {
+        assert_eq!(Coord::try_from_pair((2, 5)), Ok(Coord { row: 2, col: 5 }));
+        assert!(Coord::try_from_pair((usize::MAX, 0)).is_err());
+        assert!(Coord::try_from_pair((0, isize::MAX as usize + 1)).is_err());
+    }
+
+    #[test]
+    fn within_manhattan() {
+        let center = Coord { row: 2, col: -3 };
+        assert_eq!(
+            center.within_manhattan(0).collect::<Vec<_>>(),
+            [(center, 0)]
+        );
+
+        for radius in 1..5_u16 {
+            let coords: Vec<_> = center.within_manhattan(radius).collect();
+            let radius_usize = radius as usize;
+            assert_eq!(coords.len(), 2 * radius_usize * (radius_usize + 1) + 1);
+            assert!(coords.iter().all(|(coord, distance)| {
+                (*distance <= radius) && (center.manhattan_distance(coord) == *distance as usize)
+            }));
+        }
+    }
+
+    #[test]
+    fn bounding_box_empty() {
+        assert_eq!(bounding_box(std::iter::empty()), None);
+    }
+
+    #[test]
+    fn coord_minmax_vs_bounding_box() {
+        let coords = [
+            Coord { row: 1, col: 5 },
+            Coord { row: 3, col: 0 },
+            Coord { row: 1, col: 2 },
+            Coord { row: 2, col: 7 },
+        ];
+
+        // The extremes are actual points, ordered by row first.
+        assert_eq!(
+            coord_minmax(coords.iter().copied()),
+            Some((Coord { row: 1, col: 2 }, Coord { row: 3, col: 0 }))
+        );
+
+        // The bounding box' corners combine components of different points.
+        assert_eq!(
+            bounding_box(coords.iter().copied()),
+            Some((Coord { row: 1, col: 0 }, Coord { row: 3, col: 7 }))
+        );
+
+        let single = Coord { row: -4, col: 9 };
+        assert_eq!(
+            coord_minmax(std::iter::once(single)),
+            Some((single, single))
+        );
+    }
+
+    #[test]
+    fn coord_minmax_empty() {
+        assert_eq!(coord_minmax(std::iter::empty()), None);
+    }
+
+    #[test]
+    fn bounding_box_scattered() {
+        let coords = [
+            Coord { row: 3, col: -1 },
+            Coord { row: 0, col: 4 },
+            Coord { row: 7, col: 2 },
+        ];
+        assert_eq!(
+            bounding_box(coords.into_iter()),
+            Some((Coord { row: 0, col: -1 }, Coord { row: 7, col: 4 }))
+        );
+    }
+
+    #[test]
+    fn line_segments() {
+        let to_coords = |pairs: &[(isize, isize)]| -> Vec<Coord> {
+            pairs.iter().map(|&(row, col)| Coord { row, col }).collect()
+        };
+        let start = Coord { row: 1, col: 1 };
+
+        // Horizontal, vertical and diagonal, in both directions.
+        assert_eq!(
+            line(start, Coord { row: 1, col: 4 }).collect::<Vec<_>>(),
+            to_coords(&[(1, 1), (1, 2), (1, 3), (1, 4)])
+        );
+        assert_eq!(
+            line(start, Coord { row: -1, col: 1 }).collect::<Vec<_>>(),
+            to_coords(&[(1, 1), (0, 1), (-1, 1)])
+        );
+        assert_eq!(
+            line(start, Coord { row: 4, col: -2 }).collect::<Vec<_>>(),
+            to_coords(&[(1, 1), (2, 0), (3, -1), (4, -2)])
+        );
+        assert_eq!(line(start, start).collect::<Vec<_>>(), [start]);
+
+        // General slope, where the points are symmetric when reversed.
+        let end = Coord { row: 3, col: 6 };
+        let points: Vec<_> = line(start, end).collect();
+        assert_eq!(
+            points,
+            to_coords(&[(1, 1), (1, 2), (2, 3), (2, 4), (3, 5), (3, 6)])
+        );
+        let mut reversed: Vec<_> = line(end, start).collect();
+        reversed.reverse();
+        assert_eq!(reversed, points);
+
+        // Consecutive points are always neighbors.
+        let points: Vec<_> = line(start, Coord { row: -6, col: 3 }).collect();
+        assert_eq!(points.first(), Some(&start));
+        assert_eq!(points.last(), Some(&Coord { row: -6, col: 3 }));
+        assert!(points.windows(2).all(|e| {
+            let offset = e[1] - e[0];
+            offset.row.abs() <= 1 && offset.col.abs() <= 1
+        }));
+    }
+
+    #[test]
+    fn iter_rect_row_major() {
+        let coords: Vec<_> =
+            iter_rect(Coord { row: 1, col: 2 }, Coord { row: 2, col: 3 }).collect();
+        assert_eq!(
+            coords,
+            [
+                Coord { row: 1, col: 2 },
+                Coord { row: 1, col: 3 },
+                Coord { row: 2, col: 2 },
+                Coord { row: 2, col: 3 },
+            ]
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use rustc_hash::FxHashMap as HashMap;
+use std::hash::Hash;
+
+/// Count the number of distinct paths from the start state to any terminal
+/// state, where each step goes from a state to one of its successors. Paths
+/// end as soon as they reach a terminal state. The number of paths from each
+/// visited state is memoised, so shared parts of paths are only counted once.
+///
+/// NOTE: The successors must form a DAG, i.e. no state can be reachable from
+/// itself. Otherwise there's an infinite amount of paths.
+pub fn count_paths<S, T, U>(start: S, is_terminal: T, successors: U) -> usize
+where
+    S: Hash + Eq + Clone,
+    T: Fn(&S) -> bool,
+    U: Fn(&S) -> Vec<S>,
+{
+    fn count<S, T, U>(
+        state: &S,
+        is_terminal: &T,
+        successors: &U,
+        counts: &mut HashMap<S, usize>,
+    ) -> usize
+    where
+        S: Hash + Eq + Clone,
+        T: Fn(&S) -> bool,
+        U: Fn(&S) -> Vec<S>,
+    {
+        if is_terminal(state) {
+            return 1;
+        }
+        if let Some(&num_paths) = counts.get(state) {
+            return num_paths;
+        }
+
+        let num_paths = successors(state)
+            .iter()
+            .map(|next| count(next, is_terminal, successors, counts))
+            .sum();
+
+        counts.insert(state.clone(), num_paths);
+        num_paths
+    }
+
+    count(&start, &is_terminal, &successors, &mut HashMap::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn lattice_paths() {
+        // Paths going only down or right from the top-left to the bottom-right
+        // corner of a grid, i.e. (rows + cols) choose rows of them.
+        let num_calls = Cell::new(0);
+        let lattice_paths = |rows: usize, cols: usize| {
+            num_calls.set(0);
+            count_paths(
+                (0, 0),
+                |&pos| pos == (rows, cols),
+                |&(row, col)| {
+                    num_calls.set(num_calls.get() + 1);
+                    let mut result = Vec::new();
+                    if row < rows {
+                        result.push((row + 1, col));
+                    }
+                    if col < cols {
+                        result.push((row, col + 1));
+                    }
+                    result
+                },
+            )
+        };
+
+        assert_eq!(lattice_paths(0, 0), 1);
+        assert_eq!(lattice_paths(1, 1), 2);
+        assert_eq!(lattice_paths(2, 3), 10);
+
+        // Without memoisation this would take millions of calls.
+        assert_eq!(lattice_paths(16, 16), 601_080_390);
+        assert_eq!(num_calls.get(), 17 * 17 - 1);
+    }
+
+    #[test]
+    fn unreachable_terminal() {
+        assert_eq!(
+            count_paths(
+                0,
+                |&e| e == 5,
+                |&e| vec![e + 2, e + 4].into_iter().filter(|&e| e < 10).collect()
+            ),
+            0
+        );
+    }
+}
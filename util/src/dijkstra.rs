@@ -0,0 +1,210 @@
+use radix_heap::RadixHeapMap;
+use rustc_hash::FxHashMap as HashMap;
+use std::cmp::Reverse;
+use std::hash::Hash;
+
+/// Dijkstra's algorithm using a radix heap as priority queue. Returns the cost
+/// of the cheapest path from the start state to any goal state, together with
+/// the goal state that was reached, or None if no goal state is reachable.
+///
+/// NOTE: A radix heap requires that every key pushed onto it is not "smaller"
+/// than the last popped key (it's a max-heap, hence costs are wrapped in
+/// Reverse). For Dijkstra this holds as long as the edge costs returned by
+/// neighbours are non-negative, which is guaranteed by using usize. Costs are
+/// summed without overflow checks, so keep them well below usize::MAX.
+pub fn radix_dijkstra<S, T, U>(start: S, neighbours: T, is_goal: U) -> Option<(usize, S)>
+where
+    S: Hash + Eq + Clone,
+    T: Fn(&S) -> Vec<(S, usize)>,
+    U: Fn(&S) -> bool,
+{
+    let mut to_visit: RadixHeapMap<Reverse<usize>, S> = RadixHeapMap::new();
+    let mut costs: HashMap<S, usize> = HashMap::default();
+
+    costs.insert(start.clone(), 0);
+    to_visit.push(Reverse(0), start);
+
+    while let Some((Reverse(cur_cost), cur)) = to_visit.pop() {
+        if cur_cost > costs[&cur] {
+            continue; // Already found a cheaper path to this state.
+        }
+
+        if is_goal(&cur) {
+            return Some((cur_cost, cur));
+        }
+
+        for (next, step_cost) in neighbours(&cur) {
+            let next_cost = cur_cost + step_cost;
+            if costs.get(&next).is_some_and(|&cost| cost <= next_cost) {
+                continue;
+            }
+
+            costs.insert(next.clone(), next_cost);
+            to_visit.push(Reverse(next_cost), next);
+        }
+    }
+
+    None
+}
+
+/// Cost of the cheapest path from start to goal through a grid, where only
+/// cells set to true can be entered. Moving in a given direction costs what
+/// step_cost returns for it, and directions for which it returns None are never
+/// taken. E.g. return None for the diagonal directions to only allow moving
+/// N, E, S & W, or a higher cost for them to make diagonal moves more expensive.
+pub fn grid_dijkstra<T>(
+    grid: &nalgebra::DMatrix<bool>,
+    start: crate::Coord,
+    goal: crate::Coord,
+    step_cost: T,
+) -> Option<usize>
+where
+    T: Fn(crate::Direction) -> Option<usize>,
+{
+    use strum::IntoEnumIterator;
+
+    let is_open = |pos: &crate::Coord| crate::Get::get(grid, pos).is_some_and(|&e| e);
+    if !is_open(&start) {
+        return None;
+    }
+
+    let moves: Vec<(crate::Coord, usize)> = crate::Direction::iter()
+        .filter_map(|dir| step_cost(dir).map(|cost| (dir.to_coord(), cost)))
+        .collect();
+
+    radix_dijkstra(
+        start,
+        |&pos| {
+            moves
+                .iter()
+                .map(|&(offset, cost)| (pos + offset, cost))
+                .filter(|(next, _)| is_open(next))
+                .collect()
+        },
+        |&pos| pos == goal,
+    )
+    .map(|(cost, _)| cost)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BinaryHeap;
+
+    fn binary_heap_dijkstra(edges: &[Vec<(usize, usize)>], start: usize) -> Vec<Option<usize>> {
+        let mut costs = vec![None; edges.len()];
+        let mut to_visit = BinaryHeap::from([(Reverse(0), start)]);
+
+        while let Some((Reverse(cur_cost), cur)) = to_visit.pop() {
+            if costs[cur].is_some() {
+                continue;
+            }
+            costs[cur] = Some(cur_cost);
+            for &(next, step_cost) in &edges[cur] {
+                if costs[next].is_none() {
+                    to_visit.push((Reverse(cur_cost + step_cost), next));
+                }
+            }
+        }
+
+        costs
+    }
+
+    #[test]
+    fn matches_binary_heap() {
+        let mut state = 0x9e37_79b9_u32;
+        let mut random = |limit: u32| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state % limit) as usize
+        };
+
+        for _ in 0..20 {
+            // Sparse enough that some vertices are unreachable, and with zero
+            // cost edges to check that those are handled as well.
+            const NUM_VERTICES: usize = 30;
+            let mut edges = vec![Vec::new(); NUM_VERTICES];
+            for _ in 0..2 * NUM_VERTICES {
+                let from = random(NUM_VERTICES as u32);
+                let to = random(NUM_VERTICES as u32);
+                edges[from].push((to, random(20)));
+            }
+
+            let expected = binary_heap_dijkstra(&edges, 0);
+            for goal in 0..NUM_VERTICES {
+                let result = radix_dijkstra(0, |&v| edges[v].clone(), |&v| v == goal);
+                assert_eq!(result, expected[goal].map(|cost| (cost, goal)));
+            }
+        }
+    }
+
+    #[test]
+    fn grid_with_walls() {
+        // Walk from the top-left to the bottom-right corner, where moving down
+        // is more expensive than moving right.
+        let walls = [(0, 1), (1, 1), (3, 2), (2, 3)];
+        let result = radix_dijkstra(
+            (0, 0),
+            |&(row, col): &(usize, usize)| {
+                [((row + 1, col), 3), ((row, col + 1), 1)]
+                    .into_iter()
+                    .filter(|((row, col), _)| *row < 4 && *col < 4)
+                    .filter(|(pos, _)| !walls.contains(pos))
+                    .collect()
+            },
+            |&pos| pos == (3, 3),
+        );
+        assert_eq!(result, None);
+
+        let result = radix_dijkstra(
+            (0, 0),
+            |&(row, col): &(usize, usize)| {
+                [((row + 1, col), 3), ((row, col + 1), 1)]
+                    .into_iter()
+                    .filter(|((row, col), _)| *row < 4 && *col < 4)
+                    .filter(|(pos, _)| !walls[..3].contains(pos))
+                    .collect()
+            },
+            |&pos| pos == (3, 3),
+        );
+        assert_eq!(result, Some((12, (3, 3))));
+    }
+
+    #[test]
+    fn grid_diagonal_costs() {
+        let grid = nalgebra::DMatrix::from_element(3, 3, true);
+        let start = crate::Coord { row: 0, col: 0 };
+        let goal = crate::Coord { row: 2, col: 2 };
+
+        let cardinal = |dir: crate::Direction| match dir {
+            crate::Direction::North
+            | crate::Direction::East
+            | crate::Direction::South
+            | crate::Direction::West => Some(2),
+            _ => None,
+        };
+        let weighted = |diagonal_cost: usize| {
+            move |dir: crate::Direction| cardinal(dir).or(Some(diagonal_cost))
+        };
+
+        // Four cardinal steps, unless two diagonal ones are cheaper.
+        assert_eq!(grid_dijkstra(&grid, start, goal, cardinal), Some(8));
+        assert_eq!(grid_dijkstra(&grid, start, goal, weighted(3)), Some(6));
+        assert_eq!(grid_dijkstra(&grid, start, goal, weighted(5)), Some(8));
+
+        // A diagonal step followed by a cardinal one beats three cardinal ones.
+        let goal = crate::Coord { row: 1, col: 2 };
+        assert_eq!(grid_dijkstra(&grid, start, goal, cardinal), Some(6));
+        assert_eq!(grid_dijkstra(&grid, start, goal, weighted(3)), Some(5));
+
+        // The center can only be reached diagonally when its cardinal
+        // neighbours are walls.
+        let mut grid = grid;
+        grid[(0, 1)] = false;
+        grid[(1, 0)] = false;
+        let goal = crate::Coord { row: 1, col: 1 };
+        assert_eq!(grid_dijkstra(&grid, start, goal, cardinal), None);
+        assert_eq!(grid_dijkstra(&grid, start, goal, weighted(3)), Some(3));
+    }
+}
@@ -0,0 +1,88 @@
+/// Set of directions, stored as a bitmask. E.g. to keep track of from which
+/// directions a cell in a grid was visited. Supports all eight directions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DirectionSet(u8);
+
+impl DirectionSet {
+    pub const fn new() -> DirectionSet {
+        DirectionSet(0)
+    }
+
+    const fn mask(dir: crate::Direction) -> u8 {
+        1 << (dir as u8)
+    }
+
+    /// Adds a direction to the set. Returns whether it wasn't in the set yet.
+    pub fn insert(&mut self, dir: crate::Direction) -> bool {
+        let is_new = !self.contains(dir);
+        self.0 |= Self::mask(dir);
+        is_new
+    }
+
+    pub const fn contains(&self, dir: crate::Direction) -> bool {
+        (self.0 & Self::mask(dir)) != 0
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterates over the directions in the set, in the order in which they're
+    /// declared in Direction, i.e. first N, E, S & W, then the diagonals.
+    pub fn iter(&self) -> impl Iterator<Item = crate::Direction> + '_ {
+        use strum::IntoEnumIterator;
+        crate::Direction::iter().filter(|&dir| self.contains(dir))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CARDINALS: [crate::Direction; 4] = [
+        crate::Direction::North,
+        crate::Direction::East,
+        crate::Direction::South,
+        crate::Direction::West,
+    ];
+
+    #[test]
+    fn insert_and_contains() {
+        let mut set = DirectionSet::new();
+        assert!(set.is_empty());
+
+        for (idx, &dir) in CARDINALS.iter().enumerate() {
+            assert!(!set.contains(dir));
+            assert!(set.insert(dir));
+            assert!(!set.insert(dir));
+            assert!(!set.is_empty());
+
+            // Only the directions inserted so far are in the set.
+            for (other_idx, &other) in CARDINALS.iter().enumerate() {
+                assert_eq!(set.contains(other), other_idx <= idx);
+            }
+        }
+
+        assert!(!set.contains(crate::Direction::NorthEast));
+    }
+
+    #[test]
+    fn iteration_order() {
+        let mut set = DirectionSet::new();
+        assert_eq!(set.iter().count(), 0);
+
+        set.insert(crate::Direction::SouthWest);
+        set.insert(crate::Direction::West);
+        set.insert(crate::Direction::North);
+        set.insert(crate::Direction::South);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [
+                crate::Direction::North,
+                crate::Direction::South,
+                crate::Direction::West,
+                crate::Direction::SouthWest,
+            ]
+        );
+    }
+}
@@ -17,6 +17,13 @@ impl DisjointSetElem {
     }
 }
 
+/// Saved state of a DisjointSetWithMaxSize, see checkpoint() and restore().
+#[derive(Debug, Clone)]
+pub struct DisjointSetCheckpoint {
+    parent_or_size: Vec<DisjointSetElem>,
+    max_set_size: u16,
+}
+
 #[derive(Debug, Clone)]
 pub struct DisjointSetWithMaxSize {
     parent_or_size: Vec<DisjointSetElem>,
@@ -93,4 +100,50 @@ impl DisjointSetWithMaxSize {
     pub fn max_set_size(&self) -> u16 {
         self.max_set_size
     }
+
+    /// Save the current state, such that it can be restored later on.
+    pub fn checkpoint(&self) -> DisjointSetCheckpoint {
+        DisjointSetCheckpoint {
+            parent_or_size: self.parent_or_size.clone(),
+            max_set_size: self.max_set_size,
+        }
+    }
+
+    /// Restore a state previously saved with checkpoint(). This reuses the
+    /// existing allocation, so is cheaper than cloning the whole set.
+    pub fn restore(&mut self, checkpoint: &DisjointSetCheckpoint) {
+        assert_eq!(self.parent_or_size.len(), checkpoint.parent_or_size.len());
+        self.parent_or_size
+            .copy_from_slice(&checkpoint.parent_or_size);
+        self.max_set_size = checkpoint.max_set_size;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkpoint_restore() {
+        let mut set = DisjointSetWithMaxSize::new(8);
+        set.union(0, 1);
+        set.union(1, 2);
+        set.union(4, 5);
+
+        let checkpoint = set.checkpoint();
+        let roots: Vec<u16> = (0..8).map(|e| set.find(e)).collect();
+        assert_eq!(set.max_set_size(), 3);
+
+        set.union(2, 4);
+        set.union(6, 7);
+        set.union(7, 0);
+        assert_eq!(set.max_set_size(), 7);
+        assert_eq!(set.find(6), set.find(5));
+
+        set.restore(&checkpoint);
+        assert_eq!(set.max_set_size(), 3);
+        assert_eq!((0..8).map(|e| set.find(e)).collect::<Vec<_>>(), roots);
+        assert_ne!(set.find(2), set.find(4));
+        assert_ne!(set.find(6), set.find(7));
+    }
 }
@@ -0,0 +1,65 @@
+/// Error returned when parsing malformed puzzle input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputError {
+    /// The input doesn't contain any data.
+    Empty,
+    /// A row of a grid doesn't have the same length as the first row.
+    RaggedGrid {
+        row: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// A marker which must be present (e.g. a start position) wasn't found.
+    MissingMarker(&'static str),
+    /// A separator which must be present in a line wasn't found.
+    MissingSeparator(char),
+    /// A character which has no meaning in the input was encountered.
+    UnexpectedChar(char),
+    /// A value couldn't be parsed as an integer.
+    BadInteger(std::num::ParseIntError),
+    /// A track which must be a single path has multiple ways forward here.
+    BranchingTrack(crate::Coord),
+    /// A track which must be a single path has no way forward here, before
+    /// reaching its destination.
+    DeadEnd(crate::Coord),
+}
+
+impl std::fmt::Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            InputError::Empty => write!(f, "input is empty"),
+            InputError::RaggedGrid {
+                row,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "row {} has length {}, expected {} like the first row",
+                row, actual, expected
+            ),
+            InputError::MissingMarker(name) => write!(f, "no {} found in map", name),
+            InputError::MissingSeparator(separator) => {
+                write!(f, "missing separator '{}'", separator)
+            }
+            InputError::UnexpectedChar(c) => write!(f, "unexpected character '{}'", c),
+            InputError::BadInteger(err) => write!(f, "bad integer: {}", err),
+            InputError::BranchingTrack(pos) => write!(f, "track branches at {}", pos),
+            InputError::DeadEnd(pos) => write!(f, "track has a dead end at {}", pos),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::BadInteger(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::num::ParseIntError> for InputError {
+    fn from(err: std::num::ParseIntError) -> InputError {
+        InputError::BadInteger(err)
+    }
+}
@@ -1,8 +1,78 @@
-pub fn read_resource(file_name: &str) -> Result<String, std::io::Error> {
+/// Error returned when a resource can't be read. Its Debug representation is
+/// the same as the Display one, so that a failing unwrap() on a missing example
+/// input clearly names the missing fixture.
+pub struct ResourceError {
+    pub file_name: String,
+    pub source: std::io::Error,
+}
+
+impl std::fmt::Display for ResourceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.source.kind() {
+            std::io::ErrorKind::NotFound => write!(f, "missing fixture: {}", self.file_name),
+            _ => write!(f, "can't read {}: {}", self.file_name, self.source),
+        }
+    }
+}
+
+impl std::fmt::Debug for ResourceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for ResourceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Read the given file from the resources directory. With the gzip feature
+/// enabled, a missing file is instead read from the same path with a ".gz"
+/// extension appended, if that exists, and decompressed.
+pub fn read_resource(file_name: &str) -> Result<String, ResourceError> {
     // Unfortunately there's no environment variable with the toplevel dir (i.e. the workspace dir).
     // So need to go one directory up, assuming the directory structure is <root>/util/src.
-    let input_path: std::path::PathBuf = [env!("CARGO_MANIFEST_DIR"), "..", "resources", file_name]
+    let resource_dir: std::path::PathBuf = [env!("CARGO_MANIFEST_DIR"), "..", "resources"]
         .iter()
         .collect();
-    std::fs::read_to_string(input_path)
-}
\ No newline at end of file
+    read_resource_in(&resource_dir, file_name)
+}
+
+/// Same as read_resource(), but reads from the given directory instead.
+pub(crate) fn read_resource_in(
+    dir: &std::path::Path,
+    file_name: &str,
+) -> Result<String, ResourceError> {
+    let input_path = dir.join(file_name);
+
+    let result = std::fs::read_to_string(&input_path);
+    #[cfg(feature = "gzip")]
+    let result = match result {
+        // If there's no compressed file either, report the original one missing.
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            read_gzip(&input_path).map_err(|gzip_err| match gzip_err.kind() {
+                std::io::ErrorKind::NotFound => err,
+                _ => gzip_err,
+            })
+        }
+        _ => result,
+    };
+
+    result.map_err(|source| ResourceError {
+        file_name: file_name.to_string(),
+        source,
+    })
+}
+
+#[cfg(feature = "gzip")]
+fn read_gzip(path: &std::path::Path) -> std::io::Result<String> {
+    use std::io::Read;
+
+    let mut gzip_path = path.as_os_str().to_owned();
+    gzip_path.push(".gz");
+
+    let mut result = String::new();
+    flate2::read::GzDecoder::new(std::fs::File::open(gzip_path)?).read_to_string(&mut result)?;
+    Ok(result)
+}
@@ -15,3 +15,30 @@ impl<T> Get<crate::Coord> for na::DMatrix<T> {
         self.get((idx.row as usize, idx.col as usize))
     }
 }
+
+/// Bounds-checked mutable access to a matrix element by coordinate. Returns
+/// None if the coordinate has negative components or lies outside the matrix.
+pub fn get_mut<T>(matrix: &mut na::DMatrix<T>, idx: crate::Coord) -> Option<&mut T> {
+    match idx.has_negatives() {
+        true => None,
+        false => matrix.get_mut(idx.as_pair()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_mut_bounds() {
+        let mut matrix = na::DMatrix::from_element(2, 3, 0);
+
+        *get_mut(&mut matrix, crate::Coord { row: 1, col: 2 }).unwrap() = 5;
+        assert_eq!(matrix[(1, 2)], 5);
+        assert_eq!(matrix.iter().sum::<i32>(), 5);
+
+        for (row, col) in [(-1, 0), (0, -1), (-1, -1), (2, 0), (0, 3), (2, 3)] {
+            assert_eq!(get_mut(&mut matrix, crate::Coord { row, col }), None);
+        }
+    }
+}
@@ -1,22 +1,40 @@
 use rustc_hash::FxHashMap as HashMap;
 use rustc_hash::FxHashSet as HashSet;
+use std::hash::BuildHasher;
 
 pub type Vertex = u32;
 
+/// Set of vertices, using the graph's hasher.
+pub type VertexSet<S> = std::collections::HashSet<Vertex, S>;
+
+/// Undirected graph. The hasher used for its maps and sets can be chosen, e.g.
+/// to guard against adversarial inputs, but defaults to the fast FxHash.
+///
+/// A vertex must never be its own neighbour, e.g. the clique algorithms rely on
+/// this. Use add_neighbours_checked() if that isn't guaranteed by construction.
 #[derive(Debug, Clone)]
-pub struct Graph {
-    pub neighbours: HashMap<Vertex, HashSet<Vertex>>,
+pub struct Graph<S = rustc_hash::FxBuildHasher> {
+    pub neighbours: std::collections::HashMap<Vertex, VertexSet<S>, S>,
 }
 
 impl Graph {
     pub fn new() -> Graph {
+        Graph::with_hasher(rustc_hash::FxBuildHasher)
+    }
+}
+
+impl<S> Graph<S>
+where
+    S: BuildHasher + Default + Clone,
+{
+    pub fn with_hasher(hasher: S) -> Graph<S> {
         Graph {
-            neighbours: HashMap::default(),
+            neighbours: std::collections::HashMap::with_hasher(hasher),
         }
     }
 
-    pub fn add_vertex(&mut self, index: Vertex) -> &mut HashSet<Vertex> {
-        self.neighbours.entry(index).or_insert(HashSet::default())
+    pub fn add_vertex(&mut self, index: Vertex) -> &mut VertexSet<S> {
+        self.neighbours.entry(index).or_default()
     }
 
     pub fn add_neighbours(&mut self, index: Vertex, neighbours: &[Vertex]) {
@@ -26,12 +44,174 @@ impl Graph {
         });
     }
 
+    /// Same as add_neighbours(), but refuses to add a vertex as its own
+    /// neighbour. In that case nothing is added, and the vertex is returned as
+    /// error.
+    pub fn add_neighbours_checked(
+        &mut self,
+        index: Vertex,
+        neighbours: &[Vertex],
+    ) -> Result<(), Vertex> {
+        if neighbours.contains(&index) {
+            return Err(index);
+        }
+
+        self.add_neighbours(index, neighbours);
+        Ok(())
+    }
+
+    /// Returns the complement of this graph over the given vertices, i.e. a
+    /// graph in which two of those vertices are neighbours if and only if they
+    /// aren't in this graph. All given vertices are present in the result, even
+    /// if they don't have any neighbours.
+    pub fn complement(&self, all_vertices: &[Vertex]) -> Graph<S> {
+        let mut result = Graph::with_hasher(self.neighbours.hasher().clone());
+
+        for &vertex in all_vertices {
+            let existing_neighbours = self.neighbours.get(&vertex);
+            let complement_neighbours = result.add_vertex(vertex);
+
+            complement_neighbours.extend(all_vertices.iter().copied().filter(|other| {
+                (*other != vertex) && !existing_neighbours.is_some_and(|e| e.contains(other))
+            }));
+        }
+
+        result
+    }
+
+    /// Returns all maximal cliques, each with its vertices sorted. The cliques
+    /// are sorted by decreasing size, and lexicographically for equal sizes.
+    /// I.e. the output doesn't depend on the order in which cliques are found.
+    pub fn maximal_cliques_sorted(&self) -> Vec<Vec<Vertex>> {
+        let mut cliques: Vec<Vec<Vertex>> = Vec::new();
+        self.maximal_cliques(|clique| {
+            let mut clique = clique.to_vec();
+            clique.sort_unstable();
+            cliques.push(clique);
+        });
+
+        cliques.sort_unstable_by(|lhs, rhs| rhs.len().cmp(&lhs.len()).then_with(|| lhs.cmp(rhs)));
+        cliques
+    }
+
+    /// Returns the minimum number of edges that need to be removed to separate
+    /// the source from the sink, together with the (sorted) vertices which are
+    /// still connected to the source after removing them. Every pair of
+    /// neighbours is treated as an undirected edge with a capacity of 1.
+    pub fn min_cut_between(&self, source: Vertex, sink: Vertex) -> (u32, Vec<Vertex>) {
+        assert_ne!(source, sink);
+
+        // Max-flow using Edmonds-Karp, i.e. augment along shortest paths. The
+        // flow is stored skew-symmetric, i.e. flow[(a, b)] == -flow[(b, a)].
+        let mut flow: HashMap<(Vertex, Vertex), i32> = HashMap::default();
+        let mut cut_size = 0;
+
+        loop {
+            let mut predecessors: HashMap<Vertex, Vertex> = HashMap::default();
+            let mut visited: HashSet<Vertex> = HashSet::default();
+            let mut to_visit = std::collections::VecDeque::from([source]);
+            visited.insert(source);
+
+            while let Some(vertex) = to_visit.pop_front() {
+                if vertex == sink {
+                    break;
+                }
+
+                for &next in self.neighbours.get(&vertex).into_iter().flatten() {
+                    let residual = 1 - flow.get(&(vertex, next)).copied().unwrap_or(0);
+                    if (residual > 0) && visited.insert(next) {
+                        predecessors.insert(next, vertex);
+                        to_visit.push_back(next);
+                    }
+                }
+            }
+
+            if !visited.contains(&sink) {
+                // No augmenting path left, so the flow is maximal. The source's
+                // side of the cut is everything still reachable from it.
+                let mut source_side: Vec<_> = visited.into_iter().collect();
+                source_side.sort_unstable();
+                return (cut_size, source_side);
+            }
+
+            // All capacities are 1, so every path adds exactly 1 to the flow.
+            let mut vertex = sink;
+            while vertex != source {
+                let prev = predecessors[&vertex];
+                *flow.entry((prev, vertex)).or_default() += 1;
+                *flow.entry((vertex, prev)).or_default() -= 1;
+                vertex = prev;
+            }
+            cut_size += 1;
+        }
+    }
+
+    /// A* search for the cheapest path from start to goal. Returns its cost and
+    /// its vertices (including start and goal), or None if the goal can't be
+    /// reached. The weight of each edge is given by weight(from, to).
+    ///
+    /// NOTE: The heuristic must never overestimate the remaining cost to the
+    /// goal, otherwise the returned path might not be the cheapest one. A zero
+    /// heuristic turns this into Dijkstra's algorithm.
+    pub fn astar<W, H>(
+        &self,
+        start: Vertex,
+        goal: Vertex,
+        weight: W,
+        heuristic: H,
+    ) -> Option<(u64, Vec<Vertex>)>
+    where
+        W: Fn(Vertex, Vertex) -> u64,
+        H: Fn(Vertex) -> u64,
+    {
+        use std::cmp::Reverse;
+
+        let mut costs: HashMap<Vertex, u64> = HashMap::default();
+        let mut predecessors: HashMap<Vertex, Vertex> = HashMap::default();
+        let mut to_visit = std::collections::BinaryHeap::new();
+
+        costs.insert(start, 0);
+        to_visit.push((Reverse(heuristic(start)), Reverse(0), start));
+
+        while let Some((_, Reverse(cost), vertex)) = to_visit.pop() {
+            if vertex == goal {
+                let mut path = vec![goal];
+                while let Some(&prev) = predecessors.get(path.last().unwrap()) {
+                    path.push(prev);
+                }
+                path.reverse();
+                return Some((cost, path));
+            }
+
+            if cost > costs[&vertex] {
+                continue; // Already found a cheaper path to this vertex.
+            }
+
+            for &next in self.neighbours.get(&vertex).into_iter().flatten() {
+                let next_cost = cost + weight(vertex, next);
+                if costs.get(&next).is_some_and(|&e| e <= next_cost) {
+                    continue;
+                }
+
+                costs.insert(next, next_cost);
+                predecessors.insert(next, vertex);
+                to_visit.push((
+                    Reverse(next_cost + heuristic(next)),
+                    Reverse(next_cost),
+                    next,
+                ));
+            }
+        }
+
+        None
+    }
+
     fn bron_kerbosh<T>(
         &self,
         on_clique_fn: &mut T,
         required_vertices: &mut Vec<Vertex>,
-        mut possible_vertices: HashSet<Vertex>,
-        mut excluded_vertices: HashSet<Vertex>,
+        mut possible_vertices: VertexSet<S>,
+        mut excluded_vertices: VertexSet<S>,
     ) where
         T: FnMut(&[Vertex]),
     {
@@ -61,11 +241,11 @@ impl Graph {
                 required_vertices.push(vertex);
 
                 let neighbours = &self.neighbours[&vertex];
-                let possible_vertices_recurse: HashSet<Vertex> = neighbours
+                let possible_vertices_recurse: VertexSet<S> = neighbours
                     .intersection(&possible_vertices)
                     .copied()
                     .collect();
-                let excluded_vertices_recurse: HashSet<Vertex> = neighbours
+                let excluded_vertices_recurse: VertexSet<S> = neighbours
                     .intersection(&excluded_vertices)
                     .copied()
                     .collect();
@@ -88,8 +268,8 @@ impl Graph {
 
     fn bron_kerbosh_pivot(
         &self,
-        possible_vertices: &HashSet<Vertex>,
-        excluded_vertices: &HashSet<Vertex>,
+        possible_vertices: &VertexSet<S>,
+        excluded_vertices: &VertexSet<S>,
     ) -> Vertex {
         // Pick the vertex with the largest amount of neighbors to avoid the
         // maximum amount of recursions.
@@ -101,20 +281,73 @@ impl Graph {
     }
 }
 
+/// Directed graph. Keeps track of both the successors and predecessors of each
+/// vertex, so both can be queried cheaply.
+#[derive(Debug, Clone, Default)]
+pub struct DiGraph {
+    successors: HashMap<Vertex, HashSet<Vertex>>,
+    predecessors: HashMap<Vertex, HashSet<Vertex>>,
+}
+
+impl DiGraph {
+    pub fn new() -> DiGraph {
+        DiGraph::default()
+    }
+
+    pub fn add_vertex(&mut self, index: Vertex) {
+        self.successors.entry(index).or_default();
+        self.predecessors.entry(index).or_default();
+    }
+
+    /// Add an edge from one vertex to another. Both vertices are added to the
+    /// graph if they're not present yet.
+    pub fn add_edge(&mut self, from: Vertex, to: Vertex) {
+        self.add_vertex(from);
+        self.add_vertex(to);
+        self.successors.get_mut(&from).unwrap().insert(to);
+        self.predecessors.get_mut(&to).unwrap().insert(from);
+    }
+
+    pub fn vertices(&self) -> impl Iterator<Item = Vertex> + '_ {
+        self.successors.keys().copied()
+    }
+
+    pub fn successors(&self, index: Vertex) -> impl Iterator<Item = Vertex> + '_ {
+        self.successors.get(&index).into_iter().flatten().copied()
+    }
+
+    pub fn predecessors(&self, index: Vertex) -> impl Iterator<Item = Vertex> + '_ {
+        self.predecessors.get(&index).into_iter().flatten().copied()
+    }
+
+    /// Returns the number of edges leaving the given vertex.
+    pub fn out_degree(&self, index: Vertex) -> usize {
+        self.successors.get(&index).map_or(0, |e| e.len())
+    }
+
+    /// Returns the number of edges entering the given vertex.
+    pub fn in_degree(&self, index: Vertex) -> usize {
+        self.predecessors.get(&index).map_or(0, |e| e.len())
+    }
+}
+
 pub trait BronKerbosh {
     fn maximal_cliques<T>(&self, on_clique_fn: T)
     where
         T: FnMut(&[Vertex]);
 }
 
-impl BronKerbosh for Graph {
+impl<S> BronKerbosh for Graph<S>
+where
+    S: BuildHasher + Default + Clone,
+{
     fn maximal_cliques<T>(&self, mut on_clique_fn: T)
     where
         T: FnMut(&[Vertex]),
     {
         let mut required_vertices: Vec<Vertex> = Vec::new();
-        let possible_vertices: HashSet<Vertex> = self.neighbours.keys().copied().collect();
-        let empty_vec = HashSet::default();
+        let possible_vertices: VertexSet<S> = self.neighbours.keys().copied().collect();
+        let empty_vec = VertexSet::default();
 
         self.bron_kerbosh(
             &mut on_clique_fn,
@@ -124,3 +357,206 @@ impl BronKerbosh for Graph {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn complement_of_triangle() {
+        let mut graph = Graph::new();
+        graph.add_neighbours(0, &[1, 2]);
+        graph.add_neighbours(1, &[0, 2]);
+        graph.add_neighbours(2, &[0, 1]);
+
+        let complement = graph.complement(&[0, 1, 2]);
+        assert_eq!(complement.neighbours.len(), 3);
+        assert!(complement.neighbours.values().all(|e| e.is_empty()));
+    }
+
+    #[test]
+    fn complement_of_path() {
+        // 0 - 1 - 2, plus an isolated vertex 3 which isn't in the graph.
+        let mut graph = Graph::new();
+        graph.add_neighbours(0, &[1]);
+        graph.add_neighbours(1, &[0, 2]);
+        graph.add_neighbours(2, &[1]);
+
+        let complement = graph.complement(&[0, 1, 2, 3]);
+        let neighbours = |vertex| {
+            let mut result: Vec<_> = complement.neighbours[&vertex].iter().copied().collect();
+            result.sort();
+            result
+        };
+        assert_eq!(neighbours(0), [2, 3]);
+        assert_eq!(neighbours(1), [3]);
+        assert_eq!(neighbours(2), [0, 3]);
+        assert_eq!(neighbours(3), [0, 1, 2]);
+
+        // The largest independent set in the original graph is {0, 2, 3}.
+        let mut largest: Vec<Vertex> = Vec::new();
+        complement.maximal_cliques(|clique| {
+            if clique.len() > largest.len() {
+                largest = clique.to_vec();
+            }
+        });
+        largest.sort();
+        assert_eq!(largest, [0, 2, 3]);
+    }
+
+    #[test]
+    fn maximal_cliques_sorted() {
+        // Two triangles {1, 3, 5} and {0, 2, 4} sharing no vertices, with a
+        // single edge 4 - 6 sticking out, and an isolated vertex 7.
+        let mut graph = Graph::new();
+        for (lhs, rhs) in [(5, 3), (3, 1), (1, 5), (4, 2), (2, 0), (0, 4), (4, 6)] {
+            graph.add_neighbours(lhs, &[rhs]);
+            graph.add_neighbours(rhs, &[lhs]);
+        }
+        graph.add_vertex(7);
+
+        assert_eq!(
+            graph.maximal_cliques_sorted(),
+            [vec![0, 2, 4], vec![1, 3, 5], vec![4, 6], vec![7]]
+        );
+    }
+
+    #[test]
+    fn custom_hasher() {
+        // Same graph as in maximal_cliques_sorted(), but with std's hasher.
+        let mut graph = Graph::with_hasher(std::collections::hash_map::RandomState::new());
+        for (lhs, rhs) in [(5, 3), (3, 1), (1, 5), (4, 2), (2, 0), (0, 4), (4, 6)] {
+            graph.add_neighbours(lhs, &[rhs]);
+            graph.add_neighbours(rhs, &[lhs]);
+        }
+        graph.add_vertex(7);
+
+        let mut num_cliques = 0;
+        graph.maximal_cliques(|_| num_cliques += 1);
+        assert_eq!(num_cliques, 4);
+        assert_eq!(
+            graph.maximal_cliques_sorted(),
+            [vec![0, 2, 4], vec![1, 3, 5], vec![4, 6], vec![7]]
+        );
+
+        let complement = graph.complement(&[0, 1, 2]);
+        assert_eq!(complement.neighbours[&0].len(), 1);
+        assert_eq!(complement.neighbours[&1].len(), 2);
+    }
+
+    #[test]
+    fn self_loops() {
+        let mut graph = Graph::new();
+        assert_eq!(graph.add_neighbours_checked(0, &[1, 2]), Ok(()));
+        assert_eq!(graph.add_neighbours_checked(1, &[2, 1, 3]), Err(1));
+        assert_eq!(graph.neighbours[&0].len(), 2);
+        assert!(!graph.neighbours.contains_key(&1));
+
+        // The unchecked version adds whatever it's given.
+        graph.add_neighbours(1, &[2, 1, 3]);
+        assert_eq!(graph.neighbours[&1].len(), 3);
+        assert!(graph.neighbours[&1].contains(&1));
+    }
+
+    #[test]
+    fn min_cut_bottleneck() {
+        // Two 4-cliques {0, 1, 2, 3} and {4, 5, 6, 7}, connected by two edges.
+        let mut graph = Graph::new();
+        let mut add_edge = |lhs, rhs| {
+            graph.add_neighbours(lhs, &[rhs]);
+            graph.add_neighbours(rhs, &[lhs]);
+        };
+        for clique in [[0, 1, 2, 3], [4, 5, 6, 7]] {
+            for (idx, &lhs) in clique.iter().enumerate() {
+                for &rhs in clique[idx + 1..].iter() {
+                    add_edge(lhs, rhs);
+                }
+            }
+        }
+        add_edge(3, 4);
+        add_edge(2, 5);
+
+        assert_eq!(graph.min_cut_between(0, 7), (2, vec![0, 1, 2, 3]));
+        assert_eq!(graph.min_cut_between(6, 1), (2, vec![4, 5, 6, 7]));
+
+        // Within a clique, the cheapest cut isolates one of the vertices.
+        assert_eq!(graph.min_cut_between(0, 1).0, 3);
+
+        // Nothing to cut if there's no connection at all.
+        graph.add_vertex(8);
+        assert_eq!(graph.min_cut_between(8, 0), (0, vec![8]));
+    }
+
+    #[test]
+    fn astar_heuristics() {
+        // Vertices on a line, where vertex v sits at position 10 * v, so the
+        // distance along the line is a lower bound on the cost of any path.
+        // Every edge costs the distance between its end points, plus a detour
+        // penalty for edges which don't connect adjacent vertices.
+        let mut graph = Graph::new();
+        let edges = [
+            (0, 1),
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (0, 2),
+            (2, 4),
+            (1, 4),
+            (5, 6),
+        ];
+        for (lhs, rhs) in edges {
+            graph.add_neighbours(lhs, &[rhs]);
+            graph.add_neighbours(rhs, &[lhs]);
+        }
+        let weight = |from: Vertex, to: Vertex| {
+            let distance = 10 * from.abs_diff(to) as u64;
+            match from.abs_diff(to) {
+                1 => distance + 2,
+                2 => distance + 3,
+                _ => distance + 10,
+            }
+        };
+
+        // The cheapest path takes the two shortcuts: 23 + 23 < 4 * 12 < 12 + 40.
+        let expected_cost = 46;
+        let with_heuristic = graph.astar(0, 4, weight, |e| 10 * 4_u32.abs_diff(e) as u64);
+        let without_heuristic = graph.astar(0, 4, weight, |_| 0);
+        assert_eq!(with_heuristic, Some((expected_cost, vec![0, 2, 4])));
+        assert_eq!(without_heuristic, Some((expected_cost, vec![0, 2, 4])));
+
+        assert_eq!(graph.astar(3, 3, weight, |_| 0), Some((0, vec![3])));
+        assert_eq!(graph.astar(0, 6, weight, |_| 0), None);
+    }
+
+    #[test]
+    fn digraph_degrees() {
+        // Diamond 0 -> {1, 2} -> 3, plus 3 -> 4.
+        let mut graph = DiGraph::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 2);
+        graph.add_edge(1, 3);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 4);
+        graph.add_edge(0, 1); // Duplicate edges are ignored.
+        graph.add_vertex(5);
+
+        let mut vertices: Vec<_> = graph.vertices().collect();
+        vertices.sort();
+        assert_eq!(vertices, [0, 1, 2, 3, 4, 5]);
+
+        let degrees: Vec<_> = (0..=6)
+            .map(|e| (graph.in_degree(e), graph.out_degree(e)))
+            .collect();
+        assert_eq!(
+            degrees,
+            [(0, 2), (1, 1), (1, 1), (2, 1), (1, 0), (0, 0), (0, 0)]
+        );
+
+        let mut predecessors: Vec<_> = graph.predecessors(3).collect();
+        predecessors.sort();
+        assert_eq!(predecessors, [1, 2]);
+        assert_eq!(graph.successors(3).collect::<Vec<_>>(), [4]);
+        assert_eq!(graph.predecessors(0).count(), 0);
+        assert_eq!(graph.successors(6).count(), 0);
+    }
+}
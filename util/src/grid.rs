@@ -0,0 +1,401 @@
+use nalgebra as na;
+
+/// Returns the number of rows and columns of a grid, i.e. of the lines in the
+/// given string. Fails if the grid is empty, or if not all rows have the same
+/// length.
+pub fn grid_dimensions(s: &str) -> Result<(usize, usize), crate::InputError> {
+    let cols = s.lines().next().ok_or(crate::InputError::Empty)?.len();
+
+    let mut rows = 0;
+    for (row, line) in s.lines().enumerate() {
+        if line.len() != cols {
+            return Err(crate::InputError::RaggedGrid {
+                row,
+                expected: cols,
+                actual: line.len(),
+            });
+        }
+        rows += 1;
+    }
+
+    Ok((rows, cols))
+}
+
+/// Returns the coordinates of all markers in a grid, i.e. of all bytes for
+/// which is_marker returns true, grouped per marker. Coordinates of the same
+/// marker are in row-major order.
+pub fn marker_positions<T>(
+    s: &str,
+    is_marker: T,
+) -> std::collections::HashMap<u8, Vec<crate::Coord>>
+where
+    T: Fn(u8) -> bool,
+{
+    let mut result = std::collections::HashMap::<u8, Vec<crate::Coord>>::new();
+    for (row, line) in s.lines().enumerate() {
+        for (col, &value) in line.as_bytes().iter().enumerate() {
+            if is_marker(value) {
+                result.entry(value).or_default().push((row, col).into());
+            }
+        }
+    }
+    result
+}
+
+/// Returns the number of cells set to true.
+pub fn count_true(grid: &na::DMatrix<bool>) -> usize {
+    grid.iter().filter(|&&e| e).count()
+}
+
+/// Render the grid as a string, with the given function turning each cell into
+/// a character. Every row ends up on a line of its own, terminated by a
+/// newline, i.e. the same format as a grid in the input.
+pub fn render_grid<T, U>(grid: &na::DMatrix<T>, cell: U) -> String
+where
+    U: Fn(&T) -> char,
+{
+    let mut result = String::with_capacity(grid.nrows() * (grid.ncols() + 1));
+    for row in grid.row_iter() {
+        result.extend(row.iter().map(&cell));
+        result.push('\n');
+    }
+    result
+}
+
+/// Returns the number of distinct coordinates, i.e. each coordinate is only
+/// counted once, no matter how often it occurs.
+pub fn count_distinct_cells<T>(coords: T) -> usize
+where
+    T: Iterator<Item = crate::Coord>,
+{
+    coords.collect::<rustc_hash::FxHashSet<_>>().len()
+}
+
+/// Returns a grid of the given number of rows and columns, in which all given
+/// coordinates are set to true. Coordinates outside of the grid are ignored.
+pub fn visited_mask<T>(coords: T, (nrows, ncols): (usize, usize)) -> na::DMatrix<bool>
+where
+    T: Iterator<Item = crate::Coord>,
+{
+    let mut result = na::DMatrix::from_element(nrows, ncols, false);
+    for coord in coords {
+        if let Some(cell) = crate::get_mut(&mut result, coord) {
+            *cell = true;
+        }
+    }
+    result
+}
+
+/// Iterates over the coordinates of all cells set to true, in storage (i.e.
+/// column-major) order.
+pub fn coords_where(grid: &na::DMatrix<bool>) -> impl Iterator<Item = crate::Coord> + '_ {
+    grid.iter()
+        .enumerate()
+        .filter(|(_, &e)| e)
+        .map(|(idx, _)| crate::Coord::from_column_major_index(idx, grid.nrows(), grid.ncols()))
+}
+
+/// Iterates over all cells in row-major order, i.e. first over all columns of
+/// the first row, then over those of the second row, etc.
+pub fn iter_row_major<T>(grid: &na::DMatrix<T>) -> impl Iterator<Item = (crate::Coord, &T)> + '_ {
+    (0..grid.nrows()).flat_map(move |row| {
+        (0..grid.ncols()).map(move |col| (crate::Coord::from((row, col)), &grid[(row, col)]))
+    })
+}
+
+/// Iterates over all cells in column-major (i.e. nalgebra's storage) order,
+/// i.e. first over all rows of the first column, then over those of the second
+/// column, etc.
+pub fn iter_col_major<T>(grid: &na::DMatrix<T>) -> impl Iterator<Item = (crate::Coord, &T)> + '_ {
+    grid.iter().enumerate().map(|(idx, value)| {
+        (
+            crate::Coord::from_column_major_index(idx, grid.nrows(), grid.ncols()),
+            value,
+        )
+    })
+}
+
+/// Returns a view of the (2 * radius + 1) x (2 * radius + 1) window centered on
+/// the given cell, or None if part of that window lies outside the grid.
+pub fn view_around<T: na::Scalar>(
+    grid: &na::DMatrix<T>,
+    center: crate::Coord,
+    radius: usize,
+) -> Option<na::DMatrixView<'_, T>> {
+    let size = 2 * radius + 1;
+    let corner = center.checked_sub(crate::Coord::try_from_pair((radius, radius)).ok()?)?;
+    if corner.has_negatives()
+        || (corner.row as usize + size > grid.nrows())
+        || (corner.col as usize + size > grid.ncols())
+    {
+        return None;
+    }
+
+    Some(grid.view(corner.as_pair(), (size, size)))
+}
+
+/// Returns the transposed grid, i.e. rows become columns and vice versa.
+pub fn transpose<T: na::Scalar>(grid: &na::DMatrix<T>) -> na::DMatrix<T> {
+    na::DMatrix::from_fn(grid.ncols(), grid.nrows(), |row, col| {
+        grid[(col, row)].clone()
+    })
+}
+
+/// Returns the grid mirrored vertically, i.e. with the order of rows reversed.
+pub fn flip_rows<T: na::Scalar>(grid: &na::DMatrix<T>) -> na::DMatrix<T> {
+    let nrows = grid.nrows();
+    na::DMatrix::from_fn(nrows, grid.ncols(), |row, col| {
+        grid[(nrows - 1 - row, col)].clone()
+    })
+}
+
+/// Returns the grid mirrored horizontally, i.e. with the order of columns
+/// reversed.
+pub fn flip_cols<T: na::Scalar>(grid: &na::DMatrix<T>) -> na::DMatrix<T> {
+    let ncols = grid.ncols();
+    na::DMatrix::from_fn(grid.nrows(), ncols, |row, col| {
+        grid[(row, ncols - 1 - col)].clone()
+    })
+}
+
+/// Count the positions in the grid at which the stencil matches, i.e. for which
+/// each (offset, expected) pair in the stencil has the expected value at that
+/// position plus the offset. Stencil elements falling outside the grid never
+/// match.
+pub fn count_stencil<T: PartialEq>(grid: &na::DMatrix<T>, stencil: &[(crate::Coord, T)]) -> usize {
+    iter_row_major(grid)
+        .filter(|&(pos, _)| {
+            stencil.iter().all(|(offset, expected)| {
+                crate::Get::get(grid, &(pos + offset)).is_some_and(|value| value == expected)
+            })
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_dimensions_rectangular() {
+        assert_eq!(grid_dimensions("abc\ndef\n"), Ok((2, 3)));
+    }
+
+    #[test]
+    fn grid_dimensions_malformed() {
+        assert_eq!(grid_dimensions(""), Err(crate::InputError::Empty));
+        assert_eq!(
+            grid_dimensions("abc\nde\nfgh"),
+            Err(crate::InputError::RaggedGrid {
+                row: 1,
+                expected: 3,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    fn markers() {
+        let markers = marker_positions("a.b.\n..a.\nb#..\n", |e| e.is_ascii_lowercase());
+        assert_eq!(markers.len(), 2);
+        assert_eq!(
+            markers[&b'a'],
+            [
+                crate::Coord { row: 0, col: 0 },
+                crate::Coord { row: 1, col: 2 }
+            ]
+        );
+        assert_eq!(
+            markers[&b'b'],
+            [
+                crate::Coord { row: 0, col: 2 },
+                crate::Coord { row: 2, col: 0 }
+            ]
+        );
+
+        assert!(marker_positions("....\n", |e| e == b'a').is_empty());
+    }
+
+    #[test]
+    fn count_and_coords_of_set_cells() {
+        #[rustfmt::skip]
+        let grid = na::DMatrix::from_row_slice(2, 3, &[
+            true, false, false,
+            true, false, true,
+        ]);
+
+        assert_eq!(count_true(&grid), 3);
+        assert_eq!(count_true(&na::DMatrix::from_element(2, 2, false)), 0);
+
+        let coords: Vec<_> = coords_where(&grid).collect();
+        assert_eq!(
+            coords,
+            [
+                crate::Coord { row: 0, col: 0 },
+                crate::Coord { row: 1, col: 0 },
+                crate::Coord { row: 1, col: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn render() {
+        #[rustfmt::skip]
+        let grid = na::DMatrix::from_row_slice(2, 4, &[
+            0u8, 1, 2, 0,
+            2, 0, 0, 1,
+        ]);
+        let rendered = render_grid(&grid, |&e| match e {
+            0 => '.',
+            1 => '#',
+            _ => 'O',
+        });
+        assert_eq!(rendered, ".#O.\nO..#\n");
+
+        // Renders as columns after transposing.
+        assert_eq!(
+            render_grid(&transpose(&grid), |&e| (b'0' + e) as char),
+            "02\n10\n20\n01\n"
+        );
+    }
+
+    #[test]
+    fn distinct_cells() {
+        let coords = [
+            crate::Coord { row: 1, col: 2 },
+            crate::Coord { row: 0, col: 0 },
+            crate::Coord { row: 1, col: 2 },
+            crate::Coord { row: -1, col: 0 },
+            crate::Coord { row: 0, col: 0 },
+        ];
+        assert_eq!(count_distinct_cells(coords.into_iter()), 3);
+        assert_eq!(count_distinct_cells(std::iter::empty()), 0);
+
+        // The coordinate outside the grid is dropped.
+        let mask = visited_mask(coords.into_iter(), (2, 3));
+        #[rustfmt::skip]
+        let expected = na::DMatrix::from_row_slice(2, 3, &[
+            true, false, false,
+            false, false, true,
+        ]);
+        assert_eq!(mask, expected);
+        assert_eq!(count_true(&mask), 2);
+    }
+
+    #[test]
+    fn count_stencil_bounds() {
+        #[rustfmt::skip]
+        let grid = na::DMatrix::from_row_slice(3, 3, b"\
+            aba\
+            bab\
+            aba");
+
+        // A single element stencil simply counts that element.
+        let origin = crate::Coord { row: 0, col: 0 };
+        assert_eq!(count_stencil(&grid, &[(origin, b'a')]), 5);
+        assert_eq!(count_stencil(&grid, &[]), 9);
+
+        // Diagonal neighbours must be in bounds, so only the center matches.
+        let diagonals: Vec<_> = [(-1, -1), (-1, 1), (1, -1), (1, 1)]
+            .into_iter()
+            .map(|(row, col)| (crate::Coord { row, col }, b'a'))
+            .collect();
+        assert_eq!(count_stencil(&grid, &diagonals), 1);
+
+        // A "b" to the left of an "a".
+        let pair = [(origin, b'a'), (crate::Coord { row: 0, col: -1 }, b'b')];
+        assert_eq!(count_stencil(&grid, &pair), 3);
+    }
+
+    #[test]
+    fn row_and_col_major_order() {
+        let grid = na::DMatrix::from_row_slice(3, 4, b"abcdefghijkl");
+
+        let mut expected_row_major = Vec::new();
+        for row in 0..3 {
+            for col in 0..4 {
+                expected_row_major.push((
+                    crate::Coord { row, col },
+                    b"abcdefghijkl"[4 * row as usize + col as usize],
+                ));
+            }
+        }
+
+        let mut expected_col_major = Vec::new();
+        for col in 0..4 {
+            for row in 0..3 {
+                expected_col_major.push((
+                    crate::Coord { row, col },
+                    b"abcdefghijkl"[4 * row as usize + col as usize],
+                ));
+            }
+        }
+
+        let row_major: Vec<_> = iter_row_major(&grid)
+            .map(|(coord, &e)| (coord, e))
+            .collect();
+        let col_major: Vec<_> = iter_col_major(&grid)
+            .map(|(coord, &e)| (coord, e))
+            .collect();
+        assert_eq!(row_major, expected_row_major);
+        assert_eq!(col_major, expected_col_major);
+
+        // Every coordinate indexes the value it's paired with.
+        assert!(iter_row_major(&grid).all(|(coord, e)| grid[coord] == *e));
+        assert!(iter_col_major(&grid).all(|(coord, e)| grid[coord] == *e));
+    }
+
+    #[test]
+    fn window_around_cell() {
+        let grid = na::DMatrix::from_fn(4, 5, |row, col| 10 * row + col);
+
+        let center = crate::Coord { row: 2, col: 1 };
+        let window = view_around(&grid, center, 1).unwrap();
+        assert_eq!(window.shape(), (3, 3));
+        for row in 0..3 {
+            for col in 0..3 {
+                assert_eq!(window[(row, col)], grid[(1 + row, col)]);
+            }
+        }
+
+        // A zero radius only contains the center itself.
+        let window = view_around(&grid, center, 0).unwrap();
+        assert_eq!(window.shape(), (1, 1));
+        assert_eq!(window[(0, 0)], 21);
+
+        // Windows sticking out on any side don't exist.
+        assert!(view_around(&grid, crate::Coord { row: 0, col: 2 }, 1).is_none());
+        assert!(view_around(&grid, crate::Coord { row: 3, col: 2 }, 1).is_none());
+        assert!(view_around(&grid, crate::Coord { row: 1, col: 0 }, 1).is_none());
+        assert!(view_around(&grid, crate::Coord { row: 1, col: 4 }, 1).is_none());
+        assert!(view_around(&grid, center, 2).is_none());
+        assert!(view_around(&grid, crate::Coord { row: -1, col: 2 }, 0).is_none());
+    }
+
+    #[test]
+    fn transpose_and_flips() {
+        let grid = na::DMatrix::from_row_slice(2, 3, b"abcdef");
+
+        assert_eq!(
+            transpose(&grid),
+            na::DMatrix::from_row_slice(3, 2, b"adbecf")
+        );
+        assert_eq!(
+            flip_rows(&grid),
+            na::DMatrix::from_row_slice(2, 3, b"defabc")
+        );
+        assert_eq!(
+            flip_cols(&grid),
+            na::DMatrix::from_row_slice(2, 3, b"cbafed")
+        );
+    }
+
+    #[test]
+    fn transpose_and_flips_round_trip() {
+        let grid = na::DMatrix::from_row_slice(3, 4, b"abcdefghijkl");
+
+        assert_eq!(transpose(&transpose(&grid)), grid);
+        assert_eq!(flip_rows(&flip_rows(&grid)), grid);
+        assert_eq!(flip_cols(&flip_cols(&grid)), grid);
+    }
+}
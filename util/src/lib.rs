@@ -1,20 +1,40 @@
 mod bit;
 mod coord;
+mod count;
+mod dijkstra;
+mod direction_set;
 mod disjoint_set;
+mod error;
 mod file;
 mod get;
 mod graph;
+mod grid;
+mod math;
 mod maze;
+mod sections;
+mod simd;
 mod slice;
+mod sliding_base;
+mod stats;
 
 pub use bit::*;
 pub use coord::*;
+pub use count::*;
+pub use dijkstra::*;
+pub use direction_set::*;
 pub use disjoint_set::*;
+pub use error::*;
 pub use file::*;
 pub use get::*;
 pub use graph::*;
+pub use grid::*;
+pub use math::*;
 pub use maze::*;
+pub use sections::*;
+pub use simd::*;
 pub use slice::*;
+pub use sliding_base::*;
+pub use stats::*;
 
 pub extern crate heck;
 
@@ -38,13 +58,24 @@ macro_rules! init {
 
 pub fn teardown() {}
 
+/// Run a test after initialization. If the test panics, this panics again with
+/// the original message, so it's shown even though the test's panic is caught.
+/// A failed unwrap() of read_resource() results in a message naming the
+/// missing fixture.
 pub fn run_test<T>(test: T) -> ()
 where
     T: FnOnce() -> () + std::panic::UnwindSafe,
 {
     init!(true);
     let result = std::panic::catch_unwind(|| test());
-    assert!(result.is_ok())
+    if let Err(payload) = result {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|e| e.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "<non-string panic payload>".to_string());
+        panic!("test failed: {}", message);
+    }
 }
 
 #[macro_export]
@@ -73,3 +104,68 @@ macro_rules! run_day {
         $crate::run_day!($day, part_a, part_b)
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "missing fixture: does_not_exist.txt")]
+    fn missing_fixture() {
+        run_test(|| {
+            read_resource("does_not_exist.txt").unwrap();
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "test failed: assertion `left == right` failed")]
+    fn panic_message() {
+        run_test(|| {
+            assert_eq!(1 + 1, 3);
+        });
+    }
+
+    #[test]
+    fn missing_resource_error() {
+        run_test(|| {
+            let err = read_resource("does_not_exist.txt").unwrap_err();
+            assert_eq!(err.file_name, "does_not_exist.txt");
+            assert_eq!(err.source.kind(), std::io::ErrorKind::NotFound);
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn gzipped_resource() {
+        use std::io::Write;
+
+        run_test(|| {
+            // Fixtures aren't part of the repository, so create a compressed
+            // one on the fly in a scratch directory, next to a plain one.
+            let dir = std::env::temp_dir().join(format!("util_gzip_test_{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+
+            let contents = "....#.....\n.........#\n..^.......\n";
+            let mut encoder = flate2::write::GzEncoder::new(
+                std::fs::File::create(dir.join("compressed.txt.gz")).unwrap(),
+                flate2::Compression::default(),
+            );
+            encoder.write_all(contents.as_bytes()).unwrap();
+            encoder.finish().unwrap();
+            std::fs::write(dir.join("plain.txt"), "plain\n").unwrap();
+
+            let compressed = read_resource_in(&dir, "compressed.txt");
+            let plain = read_resource_in(&dir, "plain.txt");
+            let missing = read_resource_in(&dir, "does_not_exist.txt");
+            std::fs::remove_dir_all(&dir).unwrap();
+
+            assert_eq!(compressed.unwrap(), contents);
+
+            // Plain files are still read as before.
+            assert_eq!(plain.unwrap(), "plain\n");
+            let err = missing.unwrap_err();
+            assert_eq!(err.file_name, "does_not_exist.txt");
+            assert_eq!(err.source.kind(), std::io::ErrorKind::NotFound);
+        });
+    }
+}
@@ -0,0 +1,57 @@
+use crate::egcd;
+
+/// Solve a system of congruences x == remainder (mod modulus), given as a list
+/// of (remainder, modulus) pairs, using the Chinese remainder theorem. The
+/// moduli don't need to be coprime. Returns the combined (remainder, modulus),
+/// with 0 <= remainder < modulus, or None if the system is inconsistent.
+pub fn crt(residues: &[(i64, i64)]) -> Option<(i64, i64)> {
+    residues
+        .iter()
+        .try_fold((0_i64, 1_i64), |(rem_a, mod_a), &(rem_b, mod_b)| {
+            assert!(mod_b > 0);
+
+            // Find k such that rem_a + k * mod_a == rem_b (mod mod_b).
+            let (gcd, inv_a, _) = egcd(mod_a, mod_b);
+            let diff = rem_b - rem_a;
+            if diff.rem_euclid(gcd) != 0 {
+                return None;
+            }
+
+            // Use 128-bit intermediates, the product easily overflows.
+            let lcm = mod_a / gcd * mod_b;
+            let step = (mod_b / gcd) as i128;
+            let k = ((diff / gcd) as i128 * inv_a as i128).rem_euclid(step);
+            let rem = (rem_a as i128 + k * mod_a as i128).rem_euclid(lcm as i128);
+
+            Some((rem as i64, lcm))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crt_coprime() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(65, 103), (35, 101)]), Some((8923, 10403)));
+        assert_eq!(crt(&[(-1, 4), (0, 3)]), Some((3, 12)));
+    }
+
+    #[test]
+    fn crt_non_coprime() {
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(3, 10), (3, 15)]), Some((3, 30)));
+    }
+
+    #[test]
+    fn crt_inconsistent() {
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(0, 2), (1, 4), (0, 3)]), None);
+    }
+
+    #[test]
+    fn crt_empty() {
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+}
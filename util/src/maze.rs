@@ -8,6 +8,13 @@ pub struct Maze {
 }
 
 impl Maze {
+    const SEARCH_DIRS: [crate::Direction; 4] = [
+        crate::Direction::North,
+        crate::Direction::East,
+        crate::Direction::South,
+        crate::Direction::West,
+    ];
+
     pub fn size(&self) -> crate::Coord {
         crate::Coord {
             row: self.maze.nrows() as isize,
@@ -26,38 +33,614 @@ impl Maze {
     pub fn iter(&self) -> impl Iterator<Item = &bool> {
         self.maze.iter()
     }
+
+    /// Returns all accessible cells with exactly one accessible neighbour, in
+    /// row-major order. The start and end positions are never included, even
+    /// if they only have a single neighbour.
+    pub fn dead_ends(&self) -> Vec<crate::Coord> {
+        crate::iter_row_major(&self.maze)
+            .map(|(pos, _)| pos)
+            .filter(|pos| self.accessible(pos))
+            .filter(|&pos| (pos != self.start_pos) && (pos != self.end_pos))
+            .filter(|&pos| {
+                Self::SEARCH_DIRS
+                    .iter()
+                    .filter(|&&dir| self.accessible(&(pos + dir)))
+                    .count()
+                    == 1
+            })
+            .collect()
+    }
+
+    /// Returns the number of steps required to reach each cell from the given
+    /// position. Unreachable cells are set to usize::MAX.
+    pub fn bfs_distances(&self, from: &crate::Coord) -> na::DMatrix<usize> {
+        let mut distances =
+            na::DMatrix::from_element(self.maze.nrows(), self.maze.ncols(), usize::MAX);
+        let mut to_visit: std::collections::VecDeque<crate::Coord> = Default::default();
+
+        if self.accessible(from) {
+            distances[from] = 0;
+            to_visit.push_back(*from);
+        }
+
+        while let Some(pos) = to_visit.pop_front() {
+            let next_distance = distances[pos] + 1;
+
+            for dir in Self::SEARCH_DIRS {
+                let next_pos = pos + dir;
+                if self.accessible(&next_pos) && (distances[next_pos] == usize::MAX) {
+                    distances[next_pos] = next_distance;
+                    to_visit.push_back(next_pos);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Returns whether the goal can be reached from the given position. Cheaper
+    /// than finding a path, since the search stops as soon as the goal is
+    /// found.
+    pub fn is_reachable(&self, from: crate::Coord, to: crate::Coord) -> bool {
+        if !self.accessible(&from) || !self.accessible(&to) {
+            return false;
+        }
+
+        // The order in which cells are visited doesn't matter, so use a stack.
+        let mut marked = na::DMatrix::from_element(self.maze.nrows(), self.maze.ncols(), false);
+        let mut to_visit = vec![from];
+        marked[from] = true;
+
+        while let Some(pos) = to_visit.pop() {
+            if pos == to {
+                return true;
+            }
+
+            for dir in Self::SEARCH_DIRS {
+                let next_pos = pos + dir;
+                if self.accessible(&next_pos) && !marked[next_pos] {
+                    marked[next_pos] = true;
+                    to_visit.push(next_pos);
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Returns a shortest path from start to goal (both included), or None if
+    /// the goal can't be reached. If there's multiple shortest paths, then
+    /// the one which prefers the earliest direction in N, E, S, W order at each
+    /// step is returned. I.e. the result is always the same for a given maze.
+    pub fn shortest_path(
+        &self,
+        start: crate::Coord,
+        goal: crate::Coord,
+    ) -> Option<Vec<crate::Coord>> {
+        // Search backwards from the goal, such that walking from the start
+        // towards decreasing distances always ends up at the goal.
+        let distances = self.bfs_distances(&goal);
+        if !start.bounded_by(&self.size()) || (distances[start] == usize::MAX) {
+            return None;
+        }
+
+        let mut result = Vec::with_capacity(distances[start] + 1);
+        let mut pos = start;
+        result.push(pos);
+
+        while pos != goal {
+            pos = Self::SEARCH_DIRS
+                .iter()
+                .map(|&dir| pos + dir)
+                .find(|next_pos| {
+                    next_pos.bounded_by(&self.size()) && (distances[next_pos] == distances[pos] - 1)
+                })
+                .unwrap();
+            result.push(pos);
+        }
+
+        Some(result)
+    }
+
+    /// Returns which cells lie on at least one cheapest path from start to
+    /// end, when starting out facing start_dir. Moving one cell forward costs
+    /// step_cost, turning 90 degrees in place costs turn_cost. If the end
+    /// can't be reached, no cells are marked. Only N, E, S & W are supported.
+    pub fn optimal_path_cells(
+        &self,
+        start: crate::Coord,
+        start_dir: crate::Direction,
+        end: crate::Coord,
+        step_cost: usize,
+        turn_cost: usize,
+    ) -> na::DMatrix<bool> {
+        use std::cmp::Reverse;
+
+        let dir_idx = |dir: crate::Direction| -> usize {
+            Self::SEARCH_DIRS.iter().position(|&e| e == dir).unwrap()
+        };
+        let turns = |idx: usize| [(idx + 1) % 4, (idx + 3) % 4];
+
+        // Dijkstra, keeping track of the cost per cell & facing direction.
+        let mut costs: [na::DMatrix<usize>; 4] = std::array::from_fn(|_| {
+            na::DMatrix::from_element(self.maze.nrows(), self.maze.ncols(), usize::MAX)
+        });
+        let mut to_visit: std::collections::BinaryHeap<Reverse<(usize, crate::Coord, usize)>> =
+            Default::default();
+
+        if self.accessible(&start) {
+            to_visit.push(Reverse((0, start, dir_idx(start_dir))));
+        }
+
+        while let Some(Reverse((cost, pos, idx))) = to_visit.pop() {
+            if cost >= costs[idx][pos] {
+                continue; // Already reached more cheaply.
+            }
+            costs[idx][pos] = cost;
+
+            let next_pos = pos + Self::SEARCH_DIRS[idx];
+            if self.accessible(&next_pos) && (cost + step_cost < costs[idx][next_pos]) {
+                to_visit.push(Reverse((cost + step_cost, next_pos, idx)));
+            }
+
+            for turn_idx in turns(idx) {
+                if cost + turn_cost < costs[turn_idx][pos] {
+                    to_visit.push(Reverse((cost + turn_cost, pos, turn_idx)));
+                }
+            }
+        }
+
+        // Walk back from the end, following every move which could have led to
+        // the cheapest cost of the cell it ends up in.
+        let mut result = na::DMatrix::from_element(self.maze.nrows(), self.maze.ncols(), false);
+        if !end.bounded_by(&self.size()) {
+            return result;
+        }
+
+        let min_cost = costs.iter().map(|e| e[end]).min().unwrap();
+        if min_cost == usize::MAX {
+            return result;
+        }
+
+        let mut on_path: [na::DMatrix<bool>; 4] = std::array::from_fn(|_| result.clone());
+        let mut to_visit: Vec<(crate::Coord, usize)> = (0..4)
+            .filter(|&idx| costs[idx][end] == min_cost)
+            .map(|idx| (end, idx))
+            .collect();
+        to_visit
+            .iter()
+            .for_each(|&(pos, idx)| on_path[idx][pos] = true);
+
+        while let Some((pos, idx)) = to_visit.pop() {
+            result[pos] = true;
+            let cost = costs[idx][pos];
+
+            let mut predecessors: Vec<(crate::Coord, usize, usize)> = turns(idx)
+                .iter()
+                .map(|&turn_idx| (pos, turn_idx, turn_cost))
+                .collect();
+            predecessors.push((pos - Self::SEARCH_DIRS[idx].to_coord(), idx, step_cost));
+
+            for (prev_pos, prev_idx, move_cost) in predecessors {
+                let is_predecessor = prev_pos.bounded_by(&self.size())
+                    && !on_path[prev_idx][prev_pos]
+                    && (costs[prev_idx][prev_pos] != usize::MAX)
+                    && (costs[prev_idx][prev_pos] + move_cost == cost);
+
+                if is_predecessor {
+                    on_path[prev_idx][prev_pos] = true;
+                    to_visit.push((prev_pos, prev_idx));
+                }
+            }
+        }
+
+        result
+    }
 }
 
 impl std::str::FromStr for Maze {
-    type Err = std::string::ParseError;
+    type Err = crate::InputError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let rows = s.lines().count();
-        let cols = s.lines().next().unwrap().len();
-
-        let mut start_idx: usize = 0;
-        let mut end_idx: usize = 0;
-        let mut result = Maze {
-            maze: na::DMatrix::from_row_iterator(
-                rows,
-                cols,
-                s.lines()
-                    .flat_map(|line| line.chars())
-                    .enumerate()
-                    .inspect(|(idx, e)| match e {
-                        'S' => start_idx = *idx,
-                        'E' => end_idx = *idx,
-                        _ => (),
-                    })
-                    .map(|(_, e)| e != '#'),
-            ),
-            start_pos: crate::Coord { row: 0, col: 0 },
-            end_pos: crate::Coord { row: 0, col: 0 },
+        let maze: CharMaze = s.parse()?;
+        Ok(maze.into())
+    }
+}
+
+impl From<CharMaze> for Maze {
+    fn from(maze: CharMaze) -> Maze {
+        Maze {
+            maze: maze.maze.map(|e| e != CharMaze::WALL),
+            start_pos: maze.start_pos,
+            end_pos: maze.end_pos,
+        }
+    }
+}
+
+/// Maze in which entering a cell has a cost, e.g. to model terrain. Walls still
+/// can't be entered, whatever their cost.
+#[derive(Debug, Clone)]
+pub struct WeightedMaze {
+    pub maze: Maze,
+    pub costs: na::DMatrix<usize>,
+}
+
+impl WeightedMaze {
+    /// Panics if the cost matrix doesn't have the same shape as the maze.
+    pub fn new(maze: Maze, costs: na::DMatrix<usize>) -> WeightedMaze {
+        assert_eq!(maze.maze.shape(), costs.shape());
+        WeightedMaze { maze, costs }
+    }
+
+    /// Returns the cost of the cheapest path from start to end, i.e. the sum
+    /// of the costs of all cells entered along the way. The start cell itself
+    /// isn't entered, so doesn't count. Returns None if the end can't be
+    /// reached.
+    pub fn weighted_shortest_path(&self, start: crate::Coord, end: crate::Coord) -> Option<usize> {
+        if !self.maze.accessible(&start) || !self.maze.accessible(&end) {
+            return None;
+        }
+
+        crate::radix_dijkstra(
+            start,
+            |&pos| {
+                Maze::SEARCH_DIRS
+                    .iter()
+                    .map(|&dir| pos + dir)
+                    .filter(|next_pos| self.maze.accessible(next_pos))
+                    .map(|next_pos| (next_pos, self.costs[next_pos]))
+                    .collect()
+            },
+            |&pos| pos == end,
+        )
+        .map(|(cost, _)| cost)
+    }
+}
+
+/// Returns the only accessible neighbour of the given position other than the
+/// previous position on the track, if there is exactly one. Otherwise the track
+/// either branches or has a dead end here.
+pub fn next_track_step(
+    maze: &Maze,
+    cur_pos: crate::Coord,
+    prev_pos: Option<crate::Coord>,
+) -> Result<crate::Coord, crate::InputError> {
+    let mut next_positions = Maze::SEARCH_DIRS
+        .iter()
+        .map(|&dir| cur_pos + dir)
+        .filter(|pos| maze.accessible(pos) && (Some(*pos) != prev_pos));
+
+    let next_pos = next_positions
+        .next()
+        .ok_or(crate::InputError::DeadEnd(cur_pos))?;
+    match next_positions.next() {
+        None => Ok(next_pos),
+        Some(_) => Err(crate::InputError::BranchingTrack(cur_pos)),
+    }
+}
+
+/// Walks the corridor from one position to another, assuming it's a single
+/// track, i.e. every cell along the way (except the endpoints) has exactly two
+/// accessible neighbours. Returns the track's cells in order, including both
+/// endpoints. Returns an error if the track branches, or hits a dead end before
+/// reaching its destination.
+pub fn trace_single_path(
+    maze: &Maze,
+    from: crate::Coord,
+    to: crate::Coord,
+) -> Result<Vec<crate::Coord>, crate::InputError> {
+    if !maze.accessible(&from) {
+        return Err(crate::InputError::DeadEnd(from));
+    }
+
+    let mut path = vec![from];
+    let mut prev_pos: Option<crate::Coord> = None;
+    let mut cur_pos = from;
+
+    while cur_pos != to {
+        let next_pos = next_track_step(maze, cur_pos, prev_pos)?;
+
+        // A track can only loop back onto itself through a cell with more than
+        // two neighbours, which is detected as a branch above. This is just a
+        // safety net.
+        if path.len() == maze.maze.len() {
+            return Err(crate::InputError::BranchingTrack(cur_pos));
+        }
+
+        prev_pos = Some(cur_pos);
+        cur_pos = next_pos;
+        path.push(cur_pos);
+    }
+
+    Ok(path)
+}
+
+/// Same as Maze, but retains the original character of each tile instead of
+/// only whether it's a wall or not.
+#[derive(Debug, Clone)]
+pub struct CharMaze {
+    pub maze: na::DMatrix<char>,
+    pub start_pos: crate::Coord,
+    pub end_pos: crate::Coord,
+}
+
+impl CharMaze {
+    pub const WALL: char = '#';
+
+    pub fn size(&self) -> crate::Coord {
+        crate::Coord {
+            row: self.maze.nrows() as isize,
+            col: self.maze.ncols() as isize,
+        }
+    }
+
+    pub fn is_wall(&self, pos: &crate::Coord) -> bool {
+        self.maze[pos] == Self::WALL
+    }
+
+    pub fn accessible(&self, pos: &crate::Coord) -> bool {
+        pos.bounded_by(&self.size()) && !self.is_wall(pos)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &char> {
+        self.maze.iter()
+    }
+}
+
+impl std::str::FromStr for CharMaze {
+    type Err = crate::InputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rows, cols) = crate::grid_dimensions(s)?;
+
+        let mut start_idx: Option<usize> = None;
+        let mut end_idx: Option<usize> = None;
+        let maze = na::DMatrix::from_row_iterator(
+            rows,
+            cols,
+            s.lines()
+                .flat_map(|line| line.chars())
+                .enumerate()
+                .inspect(|(idx, e)| match e {
+                    'S' => start_idx = Some(*idx),
+                    'E' => end_idx = Some(*idx),
+                    _ => (),
+                })
+                .map(|(_, e)| e),
+        );
+
+        let start_idx = start_idx.ok_or(crate::InputError::MissingMarker("start"))?;
+        let end_idx = end_idx.ok_or(crate::InputError::MissingMarker("end"))?;
+
+        Ok(CharMaze {
+            maze,
+            start_pos: crate::Coord::from_row_major_index(start_idx, rows, cols),
+            end_pos: crate::Coord::from_row_major_index(end_idx, rows, cols),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TWO_ROUTE_MAZE: &str = "#####\n#S..#\n#.#.#\n#..E#\n#####\n";
+
+    #[test]
+    fn shortest_path_canonical() {
+        // Going east first or south first both take 4 steps. North comes
+        // before east and east before south, so the path over the top wins.
+        let maze: Maze = TWO_ROUTE_MAZE.parse().unwrap();
+        let path = maze.shortest_path(maze.start_pos, maze.end_pos).unwrap();
+        assert_eq!(
+            path,
+            [
+                crate::Coord { row: 1, col: 1 },
+                crate::Coord { row: 1, col: 2 },
+                crate::Coord { row: 1, col: 3 },
+                crate::Coord { row: 2, col: 3 },
+                crate::Coord { row: 3, col: 3 },
+            ]
+        );
+
+        // Reversed, the path first goes north, so along the east side.
+        let path = maze.shortest_path(maze.end_pos, maze.start_pos).unwrap();
+        assert_eq!(path[1], crate::Coord { row: 2, col: 3 });
+    }
+
+    #[test]
+    fn shortest_path_unreachable() {
+        let maze: Maze = "#####\n#S#E#\n#####\n".parse().unwrap();
+        assert_eq!(maze.shortest_path(maze.start_pos, maze.end_pos), None);
+        assert_eq!(
+            maze.shortest_path(maze.start_pos, maze.start_pos),
+            Some(vec![maze.start_pos])
+        );
+    }
+
+    #[test]
+    fn is_reachable() {
+        let maze: Maze = TWO_ROUTE_MAZE.parse().unwrap();
+        assert!(maze.is_reachable(maze.start_pos, maze.end_pos));
+        assert!(maze.is_reachable(maze.end_pos, maze.end_pos));
+
+        // Walls and positions outside the maze can't be reached.
+        let wall = crate::Coord { row: 2, col: 2 };
+        assert!(!maze.is_reachable(maze.start_pos, wall));
+        assert!(!maze.is_reachable(wall, maze.start_pos));
+        assert!(!maze.is_reachable(maze.start_pos, crate::Coord { row: 9, col: 1 }));
+
+        let maze: Maze = "#####\n#S#E#\n#####\n".parse().unwrap();
+        assert!(!maze.is_reachable(maze.start_pos, maze.end_pos));
+    }
+
+    const LETTERED_MAZE: &str = "#####\n#S.a#\n#B#.#\n#..E#\n#####\n";
+
+    const REINDEER_MAZE_1: &str = "###############\n#.......#....E#\n#.#.###.#.###.#\n\
+        #.....#.#...#.#\n#.###.#####.#.#\n#.#.#.......#.#\n#.#.#####.###.#\n\
+        #...........#.#\n###.#.#####.#.#\n#...#.....#.#.#\n#.#.#.###.#.#.#\n\
+        #.....#...#.#.#\n#.###.#.#.#.#.#\n#S..#.....#...#\n###############\n";
+
+    const REINDEER_MAZE_2: &str = "#################\n#...#...#...#..E#\n#.#.#.#.#.#.#.#.#\n\
+        #.#.#.#...#...#.#\n#.#.#.#.###.#.#.#\n#...#.#.#.....#.#\n#.#.#.#.#.#####.#\n\
+        #.#...#.#.#.....#\n#.#.#####.#.###.#\n#.#.#.......#...#\n#.#.###.#####.###\n\
+        #.#.#...#.....#.#\n#.#.#.#####.###.#\n#.#.#.........#.#\n#.#.#.#########.#\n\
+        #S#.............#\n#################\n";
+
+    #[test]
+    fn weighted_shortest_path() {
+        let maze: Maze = "S.E\n...\n.#.\n".parse().unwrap();
+        let costs = |shortcut_cost: usize| {
+            #[rustfmt::skip]
+            let costs = na::DMatrix::from_row_slice(3, 3, &[
+                1, shortcut_cost, 1,
+                1, 1, 1,
+                1, 1, 1,
+            ]);
+            WeightedMaze::new(maze.clone(), costs)
         };
 
-        result.start_pos = crate::Coord::from_row_major_index(start_idx, rows, cols);
-        result.end_pos = crate::Coord::from_row_major_index(end_idx, rows, cols);
+        // Going around through the second row costs 4, vs. the shortcut's cost
+        // plus 1 for entering the end.
+        let (start, end) = (maze.start_pos, maze.end_pos);
+        assert_eq!(costs(9).weighted_shortest_path(start, end), Some(4));
+        assert_eq!(costs(3).weighted_shortest_path(start, end), Some(4));
+        assert_eq!(costs(2).weighted_shortest_path(start, end), Some(3));
+
+        // Walls can't be entered, and staying put is free.
+        let wall = crate::Coord { row: 2, col: 1 };
+        assert_eq!(costs(9).weighted_shortest_path(start, wall), None);
+        assert_eq!(costs(9).weighted_shortest_path(start, start), Some(0));
+    }
+
+    #[test]
+    fn optimal_path_cells_reindeer() {
+        for (input, expected) in [(REINDEER_MAZE_1, 45), (REINDEER_MAZE_2, 64)] {
+            let maze: Maze = input.parse().unwrap();
+            let cells = maze.optimal_path_cells(
+                maze.start_pos,
+                crate::Direction::East,
+                maze.end_pos,
+                1,
+                1000,
+            );
+            assert_eq!(crate::count_true(&cells), expected);
+            assert!(cells[maze.start_pos] && cells[maze.end_pos]);
+        }
+    }
+
+    #[test]
+    fn optimal_path_cells_unit_costs() {
+        // Without turn costs, both routes around the wall are equally cheap.
+        let maze: Maze = TWO_ROUTE_MAZE.parse().unwrap();
+        let cells =
+            maze.optimal_path_cells(maze.start_pos, crate::Direction::East, maze.end_pos, 1, 0);
+        assert_eq!(crate::count_true(&cells), 8);
+
+        let maze: Maze = "#####\n#S#E#\n#####\n".parse().unwrap();
+        let cells =
+            maze.optimal_path_cells(maze.start_pos, crate::Direction::East, maze.end_pos, 1, 0);
+        assert_eq!(crate::count_true(&cells), 0);
+    }
+
+    #[test]
+    fn dead_ends() {
+        // Both start and end are at the end of a corridor, and the side branch
+        // in the middle ends in a dead end.
+        let maze: Maze = "#######\n#S...E#\n###.###\n###.###\n#######\n"
+            .parse()
+            .unwrap();
+        assert_eq!(maze.dead_ends(), [crate::Coord { row: 3, col: 3 }]);
+
+        // A loop doesn't have any dead ends.
+        let maze: Maze = TWO_ROUTE_MAZE.parse().unwrap();
+        assert!(maze.dead_ends().is_empty());
+
+        // The end is a dead end too, but the cell next to it isn't excluded.
+        let maze: Maze = "######\n#S.#.#\n#..#E#\n#.####\n######\n".parse().unwrap();
+        assert_eq!(
+            maze.dead_ends(),
+            [
+                crate::Coord { row: 1, col: 4 },
+                crate::Coord { row: 3, col: 1 }
+            ]
+        );
+    }
+
+    #[test]
+    fn trace_single_track() {
+        let maze: Maze = "#####\n#S..#\n###.#\n#E..#\n#####\n".parse().unwrap();
+        let path = trace_single_path(&maze, maze.start_pos, maze.end_pos).unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!(path.first(), Some(&maze.start_pos));
+        assert_eq!(path.last(), Some(&maze.end_pos));
+        assert!(path
+            .iter()
+            .zip(path.iter().skip(1))
+            .all(|(lhs, rhs)| lhs.manhattan_distance(rhs) == 1));
+    }
+
+    #[test]
+    fn trace_single_path_branch_or_dead_end() {
+        // The track splits in two right at the start.
+        let maze: Maze = TWO_ROUTE_MAZE.parse().unwrap();
+        assert_eq!(
+            trace_single_path(&maze, maze.start_pos, maze.end_pos),
+            Err(crate::InputError::BranchingTrack(maze.start_pos))
+        );
+
+        // The track ends before reaching the end.
+        let maze: Maze = "#######\n#S..#E#\n#######\n".parse().unwrap();
+        assert_eq!(
+            trace_single_path(&maze, maze.start_pos, maze.end_pos),
+            Err(crate::InputError::DeadEnd(crate::Coord { row: 1, col: 3 }))
+        );
+        assert_eq!(
+            next_track_step(&maze, maze.start_pos, None),
+            Ok(crate::Coord { row: 1, col: 2 })
+        );
+    }
+
+    #[test]
+    fn malformed_mazes() {
+        assert_eq!(
+            "#####\n#S..#\n#####\n".parse::<CharMaze>().unwrap_err(),
+            crate::InputError::MissingMarker("end")
+        );
+        assert_eq!(
+            "#####\n#S.E\n#####\n".parse::<Maze>().unwrap_err(),
+            crate::InputError::RaggedGrid {
+                row: 1,
+                expected: 5,
+                actual: 4
+            }
+        );
+    }
+
+    #[test]
+    fn char_maze_preserves_tiles() {
+        let maze: CharMaze = LETTERED_MAZE.parse().unwrap();
+        assert_eq!(maze.size(), crate::Coord { row: 5, col: 5 });
+        assert_eq!(maze.start_pos, crate::Coord { row: 1, col: 1 });
+        assert_eq!(maze.end_pos, crate::Coord { row: 3, col: 3 });
+        assert_eq!(maze.maze[(1, 3)], 'a');
+        assert_eq!(maze.maze[(2, 1)], 'B');
+
+        assert!(maze.accessible(&crate::Coord { row: 2, col: 1 }));
+        assert!(!maze.accessible(&crate::Coord { row: 2, col: 2 }));
+        assert!(!maze.accessible(&crate::Coord { row: 5, col: 1 }));
+    }
 
-        Ok(result)
+    #[test]
+    fn maze_from_char_maze() {
+        let char_maze: CharMaze = LETTERED_MAZE.parse().unwrap();
+        let maze: Maze = LETTERED_MAZE.parse().unwrap();
+        assert_eq!(maze.start_pos, char_maze.start_pos);
+        assert_eq!(maze.end_pos, char_maze.end_pos);
+        assert!(maze
+            .iter()
+            .zip(char_maze.iter())
+            .all(|(&open, &tile)| open == (tile != CharMaze::WALL)));
     }
 }
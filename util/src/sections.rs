@@ -0,0 +1,123 @@
+/// Byte ranges of all sections in the input, i.e. of all maximal runs of
+/// non-blank lines. The ranges exclude the final newline of each section.
+fn section_ranges(input: &str) -> Vec<std::ops::Range<usize>> {
+    let mut result: Vec<std::ops::Range<usize>> = Vec::new();
+    let mut in_section = false;
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        match (content.trim().is_empty(), in_section) {
+            (false, false) => result.push(offset..offset + content.len()),
+            (false, true) => result.last_mut().unwrap().end = offset + content.len(),
+            (true, _) => {}
+        }
+        in_section = !content.trim().is_empty();
+        offset += line.len();
+    }
+
+    result
+}
+
+/// Split the input into a top and a bottom section around the first blank
+/// line(s), e.g. rules and updates in day 5. Any leading and trailing blank
+/// lines are ignored. The bottom section contains everything after the top
+/// one, so might consist of multiple sections itself. If there's only a single
+/// section, the bottom one is empty.
+pub fn split_sections(input: &str) -> (&str, &str) {
+    let ranges = section_ranges(input);
+    match (ranges.first(), ranges.last()) {
+        (Some(top), Some(last)) if ranges.len() > 1 => {
+            (&input[top.clone()], &input[ranges[1].start..last.end])
+        }
+        (Some(top), _) => (&input[top.clone()], ""),
+        _ => ("", ""),
+    }
+}
+
+/// Split the input into all of its blank line delimited sections. Leading and
+/// trailing blank lines, as well as multiple consecutive ones, don't result in
+/// empty sections.
+pub fn split_sections_n(input: &str) -> Vec<&str> {
+    section_ranges(input)
+        .into_iter()
+        .map(|range| &input[range])
+        .collect()
+}
+
+/// Strip the leading whitespace all non-blank lines have in common, e.g. to use
+/// an indented string literal as input. Relative indentation is preserved, and
+/// blank lines end up empty. Line endings are normalized to a single newline.
+pub fn dedent(input: &str) -> String {
+    let is_indent = |c: char| c == ' ' || c == '\t';
+    let indent = input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start_matches(is_indent).len())
+        .min()
+        .unwrap_or(0);
+
+    let mut result = String::with_capacity(input.len());
+    for line in input.lines() {
+        if !line.trim().is_empty() {
+            result.push_str(&line[indent..]);
+        }
+        result.push('\n');
+    }
+
+    // Keep the input's trailing newline, or the lack thereof.
+    if !input.ends_with('\n') {
+        result.pop();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_sections() {
+        assert_eq!(
+            split_sections("a|b\nc|d\n\n1,2\n3,4\n"),
+            ("a|b\nc|d", "1,2\n3,4")
+        );
+        assert_eq!(split_sections("a\n\nb"), ("a", "b"));
+        assert_eq!(split_sections("a\r\n\r\nb\r\n"), ("a", "b"));
+
+        // Surrounding and repeated blank lines are skipped.
+        assert_eq!(split_sections("\n\na\nb\n\n\n  \nc\n\n"), ("a\nb", "c"));
+    }
+
+    #[test]
+    fn single_section() {
+        assert_eq!(split_sections("a\nb\n"), ("a\nb", ""));
+        assert_eq!(split_sections("\na\n\n"), ("a", ""));
+        assert_eq!(split_sections(""), ("", ""));
+        assert_eq!(split_sections("\n\n"), ("", ""));
+    }
+
+    #[test]
+    fn many_sections() {
+        let input = "\na\nb\n\nc\n\n\nd\ne\n\n";
+        assert_eq!(split_sections_n(input), ["a\nb", "c", "d\ne"]);
+        assert_eq!(split_sections(input), ("a\nb", "c\n\n\nd\ne"));
+        assert!(split_sections_n("\n \n").is_empty());
+    }
+
+    #[test]
+    fn dedent_common_indent() {
+        let input = "
+    47|53
+      97|13
+
+    75,47
+";
+        assert_eq!(dedent(input), "\n47|53\n  97|13\n\n75,47\n");
+
+        // Lines of only whitespace don't count towards the common indent.
+        assert_eq!(dedent("  a\n \n    b"), "a\n\n  b");
+        assert_eq!(dedent("a\n  b\n"), "a\n  b\n");
+        assert_eq!(dedent(""), "");
+    }
+}
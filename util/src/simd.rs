@@ -0,0 +1,18 @@
+/// Number of SIMD lanes to use, given the lane count a day was tuned for. This
+/// can be overridden for all days at once by enabling one of the simd-lanes-N
+/// features, e.g. to drop to 8 lanes on targets where 16 lane operations are
+/// emulated, or to try 32 or 64 lanes with AVX-512. If multiple of those
+/// features are enabled, the smallest lane count wins.
+pub const fn simd_lanes(default: usize) -> usize {
+    if cfg!(feature = "simd-lanes-8") {
+        8
+    } else if cfg!(feature = "simd-lanes-16") {
+        16
+    } else if cfg!(feature = "simd-lanes-32") {
+        32
+    } else if cfg!(feature = "simd-lanes-64") {
+        64
+    } else {
+        default
+    }
+}
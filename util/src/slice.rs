@@ -8,6 +8,19 @@ where
     ) -> DirectedSliceIterator<'a, Self> {
         DirectedSliceIterator::new(self, coord_range)
     }
+
+    /// Same as slice(), but instead of returning an empty iterator when part
+    /// of the range is out of bounds, iterates over the in-bounds elements (and
+    /// their coordinates) until the first out-of-bounds coordinate is reached.
+    fn clipped_slice<'a>(
+        &'a self,
+        coord_range: crate::DirectedCoordRange,
+    ) -> ClippedSliceIterator<'a, Self> {
+        ClippedSliceIterator {
+            data: self,
+            iter: coord_range.iter(),
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -63,3 +76,129 @@ where
         self.iter.next().and_then(|e| Some(&self.data[e]))
     }
 }
+
+#[derive(Copy, Clone, Debug)]
+pub struct ClippedSliceIterator<'a, T>
+where
+    T: ?Sized,
+{
+    data: &'a T,
+    iter: crate::DirectedCoordRangeIterator,
+}
+
+impl<'a, T> Iterator for ClippedSliceIterator<'a, T>
+where
+    T: crate::Get<crate::Coord> + ?Sized,
+{
+    type Item = (crate::Coord, &'a <T as crate::Get<crate::Coord>>::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let coord = self.iter.next()?;
+        match self.data.get(&coord) {
+            Some(value) => Some((coord, value)),
+            None => {
+                // Once out of bounds, a straight line never comes back in.
+                // Exhaust the iterator so it doesn't resume later on.
+                self.iter.by_ref().for_each(drop);
+                None
+            }
+        }
+    }
+}
+
+/// Iterates over all pairs of adjacent elements, i.e. (s[0], s[1]), (s[1], s[2]),
+/// etc. Yields nothing for slices with less than two elements.
+pub fn pairs<T: Copy>(slice: &[T]) -> impl Iterator<Item = (T, T)> + '_ {
+    slice.windows(2).map(|e| (e[0], e[1]))
+}
+
+/// Same as pairs(), but for three adjacent elements at a time.
+pub fn triples<T: Copy>(slice: &[T]) -> impl Iterator<Item = (T, T, T)> + '_ {
+    slice.windows(3).map(|e| (e[0], e[1], e[2]))
+}
+
+/// Returns the middle element, or the lower of the two middle ones (i.e. the
+/// one with the lowest index) for an even number of elements. Returns None for
+/// an empty slice.
+pub fn middle<T: Copy>(slice: &[T]) -> Option<T> {
+    match slice.len() {
+        0 => None,
+        len => Some(slice[(len - 1) / 2]),
+    }
+}
+
+/// Sum of the middle() elements of each slice. Empty slices are skipped.
+pub fn sum_middles<T, U, V>(slices: V) -> T
+where
+    T: Copy + std::iter::Sum<T>,
+    U: AsRef<[T]>,
+    V: IntoIterator<Item = U>,
+{
+    slices.into_iter().filter_map(|e| middle(e.as_ref())).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl DirectedSlice for nalgebra::DMatrix<u8> {}
+
+    #[test]
+    fn clipped_slice_truncated() {
+        let data = nalgebra::DMatrix::from_row_slice(3, 3, &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let range = crate::DirectedCoordRange {
+            start: crate::Coord { row: 1, col: 1 },
+            len: 4,
+            dir: crate::Direction::East,
+        };
+
+        // The regular slice refuses ranges which don't fit entirely.
+        assert_eq!(DirectedSlice::slice(&data, range).count(), 0);
+
+        let clipped: Vec<_> = data.clipped_slice(range).collect();
+        assert_eq!(
+            clipped,
+            [
+                (crate::Coord { row: 1, col: 1 }, &5),
+                (crate::Coord { row: 1, col: 2 }, &6),
+            ]
+        );
+    }
+
+    #[test]
+    fn clipped_slice_starts_out_of_bounds() {
+        let data = nalgebra::DMatrix::from_row_slice(2, 2, &[1, 2, 3, 4]);
+        let range = crate::DirectedCoordRange {
+            start: crate::Coord { row: -1, col: 0 },
+            len: 3,
+            dir: crate::Direction::South,
+        };
+        assert_eq!(data.clipped_slice(range).count(), 0);
+    }
+
+    #[test]
+    fn adjacent_pairs_and_triples() {
+        let data = [1, 2, 3, 4];
+        assert_eq!(pairs(&data).collect::<Vec<_>>(), [(1, 2), (2, 3), (3, 4)]);
+        assert_eq!(triples(&data).collect::<Vec<_>>(), [(1, 2, 3), (2, 3, 4)]);
+
+        // Too short to form a single pair or triple.
+        assert_eq!(pairs::<u8>(&[]).count(), 0);
+        assert_eq!(triples::<u8>(&[]).count(), 0);
+        assert_eq!(pairs(&[1]).count(), 0);
+        assert_eq!(triples(&[1, 2]).count(), 0);
+    }
+
+    #[test]
+    fn middle_elements() {
+        assert_eq!(middle(&[75, 47, 61, 53, 29]), Some(61));
+        assert_eq!(middle(&[7]), Some(7));
+        assert_eq!(middle(&[1, 2, 3, 4]), Some(2));
+        assert_eq!(middle(&[1, 2]), Some(1));
+        assert_eq!(middle::<u8>(&[]), None);
+
+        let slices = vec![vec![75, 47, 61, 53, 29], vec![], vec![1, 2, 3, 4]];
+        assert_eq!(sum_middles(&slices), 63);
+        assert_eq!(sum_middles::<u32, &[u32], _>([]), 0);
+    }
+}
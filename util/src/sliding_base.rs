@@ -0,0 +1,134 @@
+/// Sliding window over the last LEN digits pushed into it, with each digit in
+/// 0..BASE. The window is encoded as a LEN-digit number in base BASE, with the
+/// oldest digit as most significant one. Hence all possible windows map onto
+/// the contiguous range 0..BASE.pow(LEN), which makes the encoding usable as
+/// e.g. an index into a Vec, or as a rolling hash.
+///
+/// Until LEN digits have been pushed, the missing (oldest) digits are 0.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SlidingBase<const BASE: usize, const LEN: usize> {
+    value: u32,
+}
+
+impl<const BASE: usize, const LEN: usize> SlidingBase<BASE, LEN> {
+    pub const BASE: usize = BASE;
+    pub const LENGTH: usize = LEN;
+    pub const NUM_ENCODED_INDICES: usize = BASE.pow(LEN as u32);
+
+    pub fn new() -> Self {
+        const {
+            assert!(BASE >= 2);
+            assert!(LEN >= 1);
+            assert!(Self::NUM_ENCODED_INDICES <= u32::MAX as usize);
+        }
+        Self { value: 0 }
+    }
+
+    /// Push a new digit into the window, dropping the oldest one.
+    /// Returns the encoding for the window.
+    pub fn push_and_encode(&mut self, digit: u32) -> u32 {
+        assert!((digit as usize) < BASE);
+        self.value %= BASE.pow((LEN - 1) as u32) as u32; // Remove "MSB".
+        self.value *= BASE as u32; // Shift all "digits" one place up.
+        self.value += digit;
+
+        // NOTE: This assert doesn't affect benchmarked time.
+        assert!((self.value as usize) < Self::NUM_ENCODED_INDICES);
+
+        self.value
+    }
+
+    /// Inverse of the encoding, i.e. returns the digits of the window with the
+    /// given encoding, oldest digit first.
+    pub fn decode(mut encoding: u32) -> [u32; LEN] {
+        assert!((encoding as usize) < Self::NUM_ENCODED_INDICES);
+
+        let mut digits = [0; LEN];
+        for digit in digits.iter_mut().rev() {
+            *digit = encoding % BASE as u32;
+            encoding /= BASE as u32;
+        }
+        digits
+    }
+
+    /// Returns the encoding for the window, i.e. the same value as returned by
+    /// the last call to push_and_encode().
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_encodings() {
+        let mut window = SlidingBase::<10, 3>::new();
+        let encodings: Vec<_> = [1, 2, 3, 4, 0, 9]
+            .into_iter()
+            .map(|e| window.push_and_encode(e))
+            .collect();
+        assert_eq!(encodings, [1, 12, 123, 234, 340, 409]);
+        assert_eq!(window.value(), 409);
+
+        let mut window = SlidingBase::<2, 4>::new();
+        let encodings: Vec<_> = [1, 0, 1, 1, 1, 0]
+            .into_iter()
+            .map(|e| window.push_and_encode(e))
+            .collect();
+        assert_eq!(encodings, [0b1, 0b10, 0b101, 0b1011, 0b0111, 0b1110]);
+    }
+
+    #[test]
+    fn decode_round_trip() {
+        type Window = SlidingBase<19, 4>;
+        assert_eq!(Window::decode(0), [0, 0, 0, 0]);
+        assert_eq!(Window::decode(130320), [18, 18, 18, 18]);
+
+        let mut window = Window::new();
+        let mut encoding = 0;
+        for digit in [3, 17, 0, 9, 12, 5] {
+            encoding = window.push_and_encode(digit);
+        }
+        assert_eq!(Window::decode(encoding), [0, 9, 12, 5]);
+
+        for encoding in (0..Window::NUM_ENCODED_INDICES as u32).step_by(97) {
+            let mut window = Window::new();
+            for digit in Window::decode(encoding) {
+                window.push_and_encode(digit);
+            }
+            assert_eq!(window.value(), encoding);
+        }
+    }
+
+    #[test]
+    fn encoding_in_range() {
+        type Window = SlidingBase<19, 4>;
+        assert_eq!(Window::NUM_ENCODED_INDICES, 130321);
+
+        // All maximum digits gives the largest possible encoding.
+        let mut window = Window::new();
+        for _ in 0..Window::LENGTH {
+            window.push_and_encode(18);
+        }
+        assert_eq!(window.value() as usize, Window::NUM_ENCODED_INDICES - 1);
+
+        // Some pseudo-random digits never leave the range.
+        let mut window = Window::new();
+        let mut state = 0x1234_5678_u32;
+        for _ in 0..10_000 {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            let encoding = window.push_and_encode(state % Window::BASE as u32);
+            assert!((encoding as usize) < Window::NUM_ENCODED_INDICES);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn digit_out_of_range() {
+        SlidingBase::<19, 4>::new().push_and_encode(19);
+    }
+}
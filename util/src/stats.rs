@@ -0,0 +1,110 @@
+/// Calculate the mean and (population) variance in a single pass, using
+/// Welford's algorithm. This stays accurate even if the values are large
+/// compared to their spread. Returns None if there are no values.
+pub fn mean_and_variance<T, U>(data: T) -> Option<(f64, f64)>
+where
+    T: IntoIterator<Item = U>,
+    U: Into<f64>,
+{
+    let mut count = 0_usize;
+    let mut mean = 0_f64;
+    let mut sum_squared_diffs = 0_f64;
+
+    for value in data {
+        let value: f64 = value.into();
+        count += 1;
+
+        let diff = value - mean;
+        mean += diff / count as f64;
+        sum_squared_diffs += diff * (value - mean);
+    }
+
+    match count {
+        0 => None,
+        _ => Some((mean, sum_squared_diffs / count as f64)),
+    }
+}
+
+pub fn mean<T, U>(data: T) -> Option<f64>
+where
+    T: IntoIterator<Item = U>,
+    U: Into<f64>,
+{
+    mean_and_variance(data).map(|(mean, _)| mean)
+}
+
+/// Population variance, i.e. the mean of the squared differences with the mean.
+pub fn variance<T, U>(data: T) -> Option<f64>
+where
+    T: IntoIterator<Item = U>,
+    U: Into<f64>,
+{
+    mean_and_variance(data).map(|(_, variance)| variance)
+}
+
+/// Population standard deviation.
+pub fn std_dev<T, U>(data: T) -> Option<f64>
+where
+    T: IntoIterator<Item = U>,
+    U: Into<f64>,
+{
+    variance(data).map(f64::sqrt)
+}
+
+/// Statistical dispersion, i.e. the variance divided by the mean. Values which
+/// are more clustered together have a lower dispersion.
+pub fn dispersion<T, U>(data: T) -> Option<f64>
+where
+    T: IntoIterator<Item = U>,
+    U: Into<f64>,
+{
+    mean_and_variance(data).map(|(mean, variance)| variance / mean)
+}
+
+/// Sum of the values and sum of their squares, using integer arithmetic. Useful
+/// for an exact (rounded down) integer mean and variance, when the values are
+/// small enough not to overflow.
+pub fn sum_and_sum_squared<T, U>(data: T) -> (u32, u32)
+where
+    T: IntoIterator<Item = U>,
+    U: Into<u32>,
+{
+    data.into_iter().fold((0, 0), |(sum, sum_squared), value| {
+        let value: u32 = value.into();
+        (sum + value, sum_squared + value * value)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hand_computed() {
+        let data = [2_u8, 4, 4, 4, 5, 5, 7, 9];
+        assert_eq!(mean(data), Some(5.0));
+        assert_eq!(variance(data), Some(4.0));
+        assert_eq!(std_dev(data), Some(2.0));
+        assert_eq!(dispersion(data), Some(0.8));
+
+        assert_eq!(mean_and_variance([3.5_f32]), Some((3.5, 0.0)));
+        assert_eq!(mean(Vec::<u32>::new()), None);
+        assert_eq!(dispersion(std::iter::empty::<f64>()), None);
+    }
+
+    #[test]
+    fn integer_sums() {
+        assert_eq!(sum_and_sum_squared([2_u8, 4, 4, 4, 5, 5, 7, 9]), (40, 232));
+        assert_eq!(sum_and_sum_squared(Vec::<u16>::new()), (0, 0));
+    }
+
+    #[test]
+    fn large_offset() {
+        // The naive sum of squares approach loses all precision here.
+        let offset = 1e9;
+        let (mean, variance) =
+            mean_and_variance([4.0, 7.0, 13.0, 16.0].map(|e| offset + e)).unwrap();
+        assert!((mean - (offset + 10.0)).abs() < 1e-6);
+        assert!((variance - 22.5).abs() < 1e-6);
+    }
+}